@@ -0,0 +1,109 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use log_tree_parser::LogContext;
+use serde_json::Value;
+use substreams_solana::pb::sf::solana::r#type::v1::InnerInstructions;
+
+use crate::{
+    pb::sf::solana::cnft::trades::v1::TradeData,
+    utils::{prepare_input_accounts, BUBBLEGUM_PROGRAM_ADDRESS},
+};
+
+const CNFT_FULFILL_BUY_DISCRIMINATOR: u64 = 703489685458627985;
+const CNFT_FULFILL_SELL_DISCRIMINATOR: u64 = 11379647865238161211;
+
+const BUBBLEGUM_TRANSFER_DISCRIMINATOR: u64 = 13422138168166593699;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+pub struct BubblegumTransferLayout {
+    root: [u8; 32],
+    dataHash: [u8; 32],
+    creatorHash: [u8; 32],
+    nonce: u64,
+    index: u32,
+}
+
+// Pool fills log their totals as json, amounts are in lamports. Only the fill's own
+// invocation is read, other fills of the transaction log their own totals.
+pub fn parse_logs(invocation: &LogContext) -> Option<(f64, f64, f64)> {
+    invocation
+        .program_logs
+        .iter()
+        .filter(|program_log| program_log.contains("royalty_paid"))
+        .filter_map(|program_log| {
+            let json_obj: Value = serde_json::from_str(program_log.trim()).ok()?;
+            Some((
+                json_obj.get("lp_fee")?.as_f64()?,
+                json_obj.get("royalty_paid")?.as_f64()?,
+                json_obj.get("total_price")?.as_f64()?,
+            ))
+        })
+        .last()
+}
+
+// The bubblegum transfer moves the leaf from the seller to the buyer.
+pub fn enrich_with_inner_instructions_data(
+    trade_data: &mut TradeData,
+    accounts: &Vec<String>,
+    inner_instructions: &Vec<InnerInstructions>,
+) -> () {
+    inner_instructions
+        .iter()
+        .flat_map(|inner_instruction| inner_instruction.instructions.iter())
+        .filter(|inner_inst| {
+            accounts[inner_inst.program_id_index as usize] == BUBBLEGUM_PROGRAM_ADDRESS
+                && inner_inst.data.len() > 8
+        })
+        .for_each(|inner_inst| {
+            let (disc_bytes, rest) = inner_inst.data.split_at(8);
+            let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+            if u64::from_le_bytes(disc_bytes_arr) != BUBBLEGUM_TRANSFER_DISCRIMINATOR {
+                return;
+            }
+            if let Ok(transfer_data) = BubblegumTransferLayout::try_from_slice(rest) {
+                let input_accounts = prepare_input_accounts(&inner_inst.accounts, accounts);
+                trade_data.leaf_id = transfer_data.index;
+                trade_data.seller = input_accounts.get(1).cloned().unwrap_or_default();
+                trade_data.buyer = input_accounts.get(3).cloned().unwrap_or_default();
+                trade_data.merkle_tree = input_accounts.get(4).cloned().unwrap_or_default();
+            }
+        });
+}
+
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+    accounts: &Vec<String>,
+    invocation: Option<&LogContext>,
+    inner_instructions: &Vec<InnerInstructions>,
+) -> Option<TradeData> {
+    let (disc_bytes, rest) = bytes_stream.split_at(8);
+    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
+
+    let mut trade_data = TradeData::default();
+    match discriminator {
+        CNFT_FULFILL_BUY_DISCRIMINATOR => {
+            // A seller accepting the pool's bid.
+            trade_data.instruction_type = "CnftFulfillBuy".to_string();
+            trade_data.category = "sell".to_string();
+        }
+        CNFT_FULFILL_SELL_DISCRIMINATOR => {
+            trade_data.instruction_type = "CnftFulfillSell".to_string();
+            trade_data.category = "buy".to_string();
+        }
+        _ => return None,
+    }
+
+    trade_data.platform = "coralcube_me_amm".to_string();
+    trade_data.currency = "SOL".to_string();
+    trade_data.signer = accounts.get(0)?.to_string();
+
+    if let Some((amm_fee, royalty, amount)) = invocation.and_then(parse_logs) {
+        trade_data.amm_fee = amm_fee;
+        trade_data.royalty = royalty;
+        trade_data.amount = amount;
+    }
+    enrich_with_inner_instructions_data(&mut trade_data, accounts, inner_instructions);
+
+    Some(trade_data)
+}
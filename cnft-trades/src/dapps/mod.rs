@@ -1,2 +1,3 @@
 pub mod dapp_M3mxk5W2tt27WGT7THox7PmgRDp4m6NEhL5xvxrBfS1;
 pub mod dapp_TCMPhJdwDryooaGtiocG1u3xcYbRpiJzb283XfCZsDp;
+pub mod dapp_mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc;
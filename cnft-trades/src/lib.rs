@@ -6,14 +6,27 @@ mod dapps;
 mod pb;
 mod utils;
 
+use log_tree_parser::{parse_logs, LogContext, LogContextIterator};
+use pb::sf::solana::bubblegum::v1::CnftCollection;
 use pb::sf::solana::cnft::trades::v1::{Output, TradeData};
 use substreams::log;
+use substreams::store::{StoreGet, StoreGetProto};
 use substreams_solana::pb::sf::solana::r#type::v1::{Block, InnerInstructions};
 use utils::convert_to_date;
+use utils::get_asset_id;
 use utils::prepare_input_accounts;
 
+const CNFT_MARKETPLACE_PROGRAMS: [&str; 3] = [
+    "TCMPhJdwDryooaGtiocG1u3xcYbRpiJzb283XfCZsDp",
+    "M3mxk5W2tt27WGT7THox7PmgRDp4m6NEhL5xvxrBfS1",
+    "mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc",
+];
+
 #[substreams::handlers::map]
-fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
+fn map_block(
+    block: Block,
+    collections_store: StoreGetProto<CnftCollection>,
+) -> Result<Output, substreams::errors::Error> {
     let slot = block.slot;
     let parent_slot = block.parent_slot;
     let timestamp = block.block_time.as_ref().unwrap().timestamp;
@@ -30,6 +43,15 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
             let post_token_balances = meta.post_token_balances;
 
             let msg = transaction.message.unwrap();
+            // Building the log tree is only worth it for the transactions we parse.
+            let parsed_logs = if accounts
+                .iter()
+                .any(|account| CNFT_MARKETPLACE_PROGRAMS.contains(&account.as_str()))
+            {
+                parse_logs(&meta.log_messages)
+            } else {
+                vec![]
+            };
 
             for (idx, inst) in msg.instructions.into_iter().enumerate() {
                 let program = &accounts[inst.program_id_index as usize];
@@ -44,6 +66,7 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
                     &inst.accounts,
                     &accounts,
                     &meta.log_messages,
+                    get_invocation(&parsed_logs, idx, None),
                     &inner_instructions,
                 );
                 if trade_data.is_some() {
@@ -56,9 +79,48 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
                     td.txn_fee = meta.fee;
                     td.instruction_index = idx as u32;
                     td.outer_program = program.to_string();
+                    td.asset_id = get_asset_id(&td.merkle_tree, td.leaf_id);
+                    td.success = meta.err.is_none();
+                    apply_collection(&mut td, &collections_store);
 
                     data.push(td);
                 }
+
+                // Aggregators and other marketplaces settle through these programs by CPI.
+                for inner_instruction in inner_instructions.iter() {
+                    for (inner_idx, inner_inst) in inner_instruction.instructions.iter().enumerate()
+                    {
+                        let inner_program = &accounts[inner_inst.program_id_index as usize];
+                        let settlement_instructions =
+                            get_settlement_instructions(inner_instruction, inner_idx);
+
+                        let trade_data = get_trade_data(
+                            inner_program,
+                            inner_inst.data.clone(),
+                            &inner_inst.accounts,
+                            &accounts,
+                            &meta.log_messages,
+                            get_invocation(&parsed_logs, idx, Some(inner_idx)),
+                            &settlement_instructions,
+                        );
+                        if trade_data.is_some() {
+                            let mut td = trade_data.unwrap();
+
+                            td.block_date = convert_to_date(timestamp);
+                            td.block_time = timestamp;
+                            td.block_slot = slot;
+                            td.tx_id = bs58::encode(&transaction.signatures[0]).into_string();
+                            td.txn_fee = meta.fee;
+                            td.instruction_index = idx as u32;
+                            td.outer_program = program.to_string();
+                            td.asset_id = get_asset_id(&td.merkle_tree, td.leaf_id);
+                            td.success = meta.err.is_none();
+                            apply_collection(&mut td, &collections_store);
+
+                            data.push(td);
+                        }
+                    }
+                }
             }
         }
     }
@@ -73,8 +135,12 @@ fn get_trade_data(
     account_indices: &Vec<u8>,
     accounts: &Vec<String>,
     log_messages: &Vec<String>,
+    invocation: Option<&LogContext>,
     inner_instructions: &Vec<InnerInstructions>,
 ) -> Option<TradeData> {
+    if instruction_data.len() < 8 {
+        return None;
+    }
     let input_accounts = prepare_input_accounts(account_indices, accounts);
 
     let mut result = None;
@@ -99,12 +165,64 @@ fn get_trade_data(
                     inner_instructions,
                 )
         }
+        "mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc" => {
+            result =
+                dapps::dapp_mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                    accounts,
+                    invocation,
+                    inner_instructions,
+                )
+        }
         _ => {}
     }
 
     return result;
 }
 
+// The invocation of an outer instruction, or of its n-th inner instruction. The log tree
+// is in invocation order, like the inner instructions of the transaction meta.
+fn get_invocation(
+    parsed_logs: &Vec<LogContext>,
+    idx: usize,
+    inner_idx: Option<usize>,
+) -> Option<&LogContext> {
+    let root = parsed_logs.get(idx)?;
+    match inner_idx {
+        Some(inner_idx) => LogContextIterator::new(&root.children_nodes).nth(inner_idx),
+        None => Some(root),
+    }
+}
+
+// Instructions run by a marketplace CPI, up to the next call into the same program.
+fn get_settlement_instructions(
+    inner_instruction: &InnerInstructions,
+    inner_idx: usize,
+) -> Vec<InnerInstructions> {
+    let program_id_index = inner_instruction.instructions[inner_idx].program_id_index;
+    let instructions = inner_instruction.instructions[inner_idx + 1..]
+        .iter()
+        .take_while(|inner_inst| inner_inst.program_id_index != program_id_index)
+        .cloned()
+        .collect();
+
+    vec![InnerInstructions {
+        index: inner_instruction.index,
+        instructions,
+        ..Default::default()
+    }]
+}
+
+// Bubblegum keys collections by tree and leaf index, trades only carry the leaf id.
+fn apply_collection(td: &mut TradeData, collections_store: &StoreGetProto<CnftCollection>) {
+    let key = format!("{}:{}", td.merkle_tree, td.leaf_id);
+    if let Some(collection) = collections_store.get_last(key) {
+        td.collection_mint = collection.collection_mint;
+        td.collection_verified = collection.verified;
+    }
+}
+
 fn filter_inner_instructions(
     meta_inner_instructions: &Vec<InnerInstructions>,
     idx: u32,
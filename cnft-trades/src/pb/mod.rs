@@ -1,6 +1,13 @@
 // @generated
 pub mod sf {
     pub mod solana {
+        pub mod bubblegum {
+            // @@protoc_insertion_point(attribute:sf.solana.bubblegum.v1)
+            pub mod v1 {
+                include!("../../../metaplex/bubblegum/src/pb/sf.solana.bubblegum.v1.rs");
+                // @@protoc_insertion_point(sf.solana.bubblegum.v1)
+            }
+        }
         pub mod cnft {
             pub mod trades {
                 // @@protoc_insertion_point(attribute:sf.solana.cnft.trades.v1)
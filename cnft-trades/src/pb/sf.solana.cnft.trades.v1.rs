@@ -50,5 +50,13 @@ pub struct TradeData {
     pub outer_program: ::prost::alloc::string::String,
     #[prost(string, required, tag="21")]
     pub platform: ::prost::alloc::string::String,
+    #[prost(string, required, tag="22")]
+    pub collection_mint: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="23")]
+    pub collection_verified: bool,
+    #[prost(string, required, tag="24")]
+    pub asset_id: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="25")]
+    pub success: bool,
 }
 // @@protoc_insertion_point(module)
@@ -1,5 +1,7 @@
 extern crate chrono;
 use chrono::prelude::*;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
 
 pub fn prepare_input_accounts(account_indices: &Vec<u8>, accounts: &Vec<String>) -> Vec<String> {
     let mut instruction_accounts: Vec<String> = vec![];
@@ -15,3 +17,34 @@ pub fn convert_to_date(ts: i64) -> String {
     let res = dt.format("%Y-%m-%d");
     return res.to_string();
 }
+
+pub const BUBBLEGUM_PROGRAM_ADDRESS: &str = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY";
+
+// The asset id is the bubblegum PDA ["asset", merkle_tree, nonce], the leaf nonce
+// equals its index in the tree.
+pub fn get_asset_id(merkle_tree: &String, leaf_id: u32) -> String {
+    let tree = match bs58::decode(merkle_tree).into_vec() {
+        Ok(tree) if tree.len() == 32 => tree,
+        _ => return "".to_string(),
+    };
+    let program_id = bs58::decode(BUBBLEGUM_PROGRAM_ADDRESS).into_vec().unwrap();
+    let nonce = (leaf_id as u64).to_le_bytes();
+
+    for bump in (0..=u8::MAX).rev() {
+        let mut hasher = Sha256::new();
+        hasher.update(b"asset");
+        hasher.update(&tree);
+        hasher.update(nonce);
+        hasher.update([bump]);
+        hasher.update(&program_id);
+        hasher.update(b"ProgramDerivedAddress");
+        let hash: [u8; 32] = hasher.finalize().into();
+
+        // A program address has to be off the ed25519 curve.
+        if CompressedEdwardsY(hash).decompress().is_none() {
+            return bs58::encode(hash).into_string();
+        }
+    }
+
+    return "".to_string();
+}
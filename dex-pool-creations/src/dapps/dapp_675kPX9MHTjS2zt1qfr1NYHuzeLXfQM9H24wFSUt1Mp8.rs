@@ -1,16 +1,27 @@
+use borsh::BorshDeserialize;
 use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
 
 use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
 
 const Initialize2: u8 = 1;
 
+// AMM v4 charges the same 0.25% trade fee on every pool.
+const TRADE_FEE_NUMERATOR: u64 = 25;
+const TRADE_FEE_DENOMINATOR: u64 = 10000;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct Initialize2Layout {
+    nonce: u8,
+    open_time: u64,
+    init_pc_amount: u64,
+    init_coin_amount: u64,
+}
+
 pub fn parse_trade_instruction(
     bytes_stream: Vec<u8>,
     input_accounts: Vec<String>,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(1);
-    let disc_bytes_arr: [u8; 1] = disc_bytes.to_vec().try_into().unwrap();
-    let discriminator: u8 = u8::from_le_bytes(disc_bytes_arr);
+    let (&discriminator, rest) = bytes_stream.split_first()?;
 
     let mut td = TradeData::default();
     let mut result = None;
@@ -18,9 +29,14 @@ pub fn parse_trade_instruction(
     match discriminator {
         Initialize2 => {
             td.dapp = "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8".to_string();
-            td.pool = input_accounts.get(4).unwrap().to_string();
-            td.base_mint = input_accounts.get(8).unwrap().to_string();
-            td.quote_mint = input_accounts.get(9).unwrap().to_string();
+            td.pool = input_accounts.get(4)?.to_string();
+            td.base_mint = input_accounts.get(8)?.to_string();
+            td.quote_mint = input_accounts.get(9)?.to_string();
+            let args = Initialize2Layout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.initial_base_amount = args.init_coin_amount;
+            td.initial_quote_amount = args.init_pc_amount;
+            td.fee_numerator = TRADE_FEE_NUMERATOR;
+            td.fee_denominator = TRADE_FEE_DENOMINATOR;
             result = Some(td);
         }
         _ => {}
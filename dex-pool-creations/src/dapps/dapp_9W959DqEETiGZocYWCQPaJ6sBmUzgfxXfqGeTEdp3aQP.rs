@@ -0,0 +1,61 @@
+use borsh::BorshDeserialize;
+use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
+
+use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+use crate::utils::{get_mint, get_token_amount};
+
+const Initialize: u8 = 0;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct FeesLayout {
+    trade_fee_numerator: u64,
+    trade_fee_denominator: u64,
+    owner_trade_fee_numerator: u64,
+    owner_trade_fee_denominator: u64,
+    owner_withdraw_fee_numerator: u64,
+    owner_withdraw_fee_denominator: u64,
+    host_fee_numerator: u64,
+    host_fee_denominator: u64,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializeLayout {
+    nonce: u8,
+    fees: FeesLayout,
+}
+
+pub fn parse_trade_instruction(
+    dapp_address: &String,
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+    post_token_balances: &Vec<TokenBalance>,
+    accounts: &Vec<String>,
+) -> Option<TradeData> {
+    let (&discriminator, rest) = bytes_stream.split_first()?;
+
+    let mut td = TradeData::default();
+    let mut result = None;
+
+    match discriminator {
+        Initialize => {
+            // The swap is funded before it is initialised, so the vault balances are the
+            // initial liquidity.
+            let token_a = input_accounts.get(2)?;
+            let token_b = input_accounts.get(3)?;
+
+            td.dapp = dapp_address.to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = get_mint(token_a, post_token_balances, accounts);
+            td.quote_mint = get_mint(token_b, post_token_balances, accounts);
+            td.initial_base_amount = get_token_amount(token_a, post_token_balances, accounts);
+            td.initial_quote_amount = get_token_amount(token_b, post_token_balances, accounts);
+            let args = InitializeLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.fee_numerator = args.fees.trade_fee_numerator + args.fees.owner_trade_fee_numerator;
+            td.fee_denominator = args.fees.trade_fee_denominator;
+            result = Some(td);
+        }
+        _ => {}
+    }
+
+    return result;
+}
@@ -0,0 +1,46 @@
+use borsh::BorshDeserialize;
+use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
+
+use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+
+const InitializeMarket: u32 = 0;
+
+const BPS_DENOMINATOR: u64 = 10000;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializeMarketLayout {
+    coin_lot_size: u64,
+    pc_lot_size: u64,
+    fee_rate_bps: u16,
+    vault_signer_nonce: u64,
+    pc_dust_threshold: u64,
+}
+
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<TradeData> {
+    // Serum-style instructions are prefixed with a version byte followed by a u32 tag.
+    let disc_bytes_arr: [u8; 4] = bytes_stream.get(1..5)?.try_into().ok()?;
+    let rest = &bytes_stream[5..];
+    let discriminator: u32 = u32::from_le_bytes(disc_bytes_arr);
+
+    let mut td = TradeData::default();
+    let mut result = None;
+
+    match discriminator {
+        InitializeMarket => {
+            td.dapp = "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin".to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = input_accounts.get(7)?.to_string();
+            td.quote_mint = input_accounts.get(8)?.to_string();
+            let args = InitializeMarketLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.fee_numerator = args.fee_rate_bps as u64;
+            td.fee_denominator = BPS_DENOMINATOR;
+            result = Some(td);
+        }
+        _ => {}
+    }
+
+    return result;
+}
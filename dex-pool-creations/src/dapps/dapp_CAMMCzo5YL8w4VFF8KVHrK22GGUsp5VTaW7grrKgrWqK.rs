@@ -1,15 +1,36 @@
+use borsh::BorshDeserialize;
 use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
 
-use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+use crate::pb::sf::solana::dex::pool::creations::v1::{FeeConfig, TradeData};
 
 const CreatePool: u64 = u64::from_le_bytes([233, 146, 209, 142, 207, 104, 64, 188]);
+const CreateAmmConfig: u64 = u64::from_le_bytes([137, 52, 237, 212, 215, 117, 108, 104]);
+const UpdateAmmConfig: u64 = u64::from_le_bytes([49, 60, 174, 136, 154, 28, 116, 200]);
+
+const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+const TRADE_FEE_RATE_PARAM: u8 = 0;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct CreateAmmConfigLayout {
+    index: u16,
+    tick_spacing: u16,
+    trade_fee_rate: u32,
+    protocol_fee_rate: u32,
+    fund_fee_rate: u32,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct UpdateAmmConfigLayout {
+    param: u8,
+    value: u32,
+}
 
 pub fn parse_trade_instruction(
     bytes_stream: Vec<u8>,
     input_accounts: Vec<String>,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(8);
-    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let mut td = TradeData::default();
@@ -18,9 +39,10 @@ pub fn parse_trade_instruction(
     match discriminator {
         CreatePool => {
             td.dapp = "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK".to_string();
-            td.pool = input_accounts.get(1).unwrap().to_string();
-            td.base_mint = input_accounts.get(3).unwrap().to_string();
-            td.quote_mint = input_accounts.get(4).unwrap().to_string();
+            td.pool = input_accounts.get(2)?.to_string();
+            td.base_mint = input_accounts.get(3)?.to_string();
+            td.quote_mint = input_accounts.get(4)?.to_string();
+            td.fee_config = input_accounts.get(1)?.to_string();
             result = Some(td);
         }
         _ => {}
@@ -28,3 +50,36 @@ pub fn parse_trade_instruction(
 
     return result;
 }
+
+// Pools charge the trade fee rate of the amm config they are created with.
+pub fn parse_fee_config_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<FeeConfig> {
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
+    let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
+
+    let fee_numerator = match discriminator {
+        CreateAmmConfig => {
+            CreateAmmConfigLayout::deserialize(&mut rest.clone())
+                .ok()?
+                .trade_fee_rate
+        }
+        UpdateAmmConfig => {
+            let args = UpdateAmmConfigLayout::deserialize(&mut rest.clone()).ok()?;
+            if args.param != TRADE_FEE_RATE_PARAM {
+                return None;
+            }
+            args.value
+        }
+        _ => return None,
+    };
+
+    Some(FeeConfig {
+        dapp: "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK".to_string(),
+        account: input_accounts.get(1)?.to_string(),
+        fee_numerator: fee_numerator as u64,
+        fee_denominator: FEE_RATE_DENOMINATOR,
+    })
+}
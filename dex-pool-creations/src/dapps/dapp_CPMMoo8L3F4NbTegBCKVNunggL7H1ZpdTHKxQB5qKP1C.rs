@@ -1,15 +1,43 @@
+use borsh::BorshDeserialize;
 use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
 
-use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+use crate::pb::sf::solana::dex::pool::creations::v1::{FeeConfig, TradeData};
 
 const Initialize: u64 = u64::from_le_bytes([175, 175, 109, 31, 13, 152, 155, 237]);
+const CreateAmmConfig: u64 = u64::from_le_bytes([137, 52, 237, 212, 215, 117, 108, 104]);
+const UpdateAmmConfig: u64 = u64::from_le_bytes([49, 60, 174, 136, 154, 28, 116, 200]);
+
+const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+const TRADE_FEE_RATE_PARAM: u8 = 0;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializeLayout {
+    init_amount_0: u64,
+    init_amount_1: u64,
+    open_time: u64,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct CreateAmmConfigLayout {
+    index: u16,
+    trade_fee_rate: u64,
+    protocol_fee_rate: u64,
+    fund_fee_rate: u64,
+    create_pool_fee: u64,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct UpdateAmmConfigLayout {
+    param: u8,
+    value: u64,
+}
 
 pub fn parse_trade_instruction(
     bytes_stream: Vec<u8>,
     input_accounts: Vec<String>,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(8);
-    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let mut td = TradeData::default();
@@ -18,9 +46,13 @@ pub fn parse_trade_instruction(
     match discriminator {
         Initialize => {
             td.dapp = "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C".to_string();
-            td.pool = input_accounts.get(3).unwrap().to_string();
-            td.base_mint = input_accounts.get(4).unwrap().to_string();
-            td.quote_mint = input_accounts.get(5).unwrap().to_string();
+            td.pool = input_accounts.get(3)?.to_string();
+            td.base_mint = input_accounts.get(4)?.to_string();
+            td.quote_mint = input_accounts.get(5)?.to_string();
+            td.fee_config = input_accounts.get(1)?.to_string();
+            let args = InitializeLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.initial_base_amount = args.init_amount_0;
+            td.initial_quote_amount = args.init_amount_1;
             result = Some(td);
         }
         _ => {}
@@ -28,3 +60,36 @@ pub fn parse_trade_instruction(
 
     return result;
 }
+
+// Pools charge the trade fee rate of the amm config they are created with.
+pub fn parse_fee_config_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<FeeConfig> {
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
+    let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
+
+    let fee_numerator = match discriminator {
+        CreateAmmConfig => {
+            CreateAmmConfigLayout::deserialize(&mut rest.clone())
+                .ok()?
+                .trade_fee_rate
+        }
+        UpdateAmmConfig => {
+            let args = UpdateAmmConfigLayout::deserialize(&mut rest.clone()).ok()?;
+            if args.param != TRADE_FEE_RATE_PARAM {
+                return None;
+            }
+            args.value
+        }
+        _ => return None,
+    };
+
+    Some(FeeConfig {
+        dapp: "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C".to_string(),
+        account: input_accounts.get(1)?.to_string(),
+        fee_numerator,
+        fee_denominator: FEE_RATE_DENOMINATOR,
+    })
+}
@@ -0,0 +1,149 @@
+use borsh::BorshDeserialize;
+use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
+
+use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+
+const InitializePermissionlessPool: u64 = u64::from_le_bytes([118, 173, 41, 157, 173, 72, 97, 103]);
+const InitializePermissionlessPoolWithFeeTier: u64 =
+    u64::from_le_bytes([6, 135, 68, 147, 229, 82, 169, 113]);
+const InitializePermissionlessConstantProductPoolWithConfig: u64 =
+    u64::from_le_bytes([7, 166, 138, 171, 206, 171, 236, 244]);
+const InitializePermissionlessConstantProductPoolWithConfig2: u64 =
+    u64::from_le_bytes([48, 149, 220, 130, 61, 11, 9, 178]);
+const InitializeCustomizablePermissionlessConstantProductPool: u64 =
+    u64::from_le_bytes([145, 24, 172, 194, 219, 125, 3, 190]);
+
+const FEE_DENOMINATOR: u64 = 100000;
+const BPS_DENOMINATOR: u64 = 10000;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct TokenMultiplierLayout {
+    token_a_multiplier: u64,
+    token_b_multiplier: u64,
+    precision_factor: u8,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub enum DepegTypeLayout {
+    #[default]
+    None,
+    Marinade,
+    Lido,
+    SplStake,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct DepegLayout {
+    base_virtual_price: u64,
+    base_cache_updated: u64,
+    depeg_type: DepegTypeLayout,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub enum CurveTypeLayout {
+    #[default]
+    ConstantProduct,
+    Stable {
+        amp: u64,
+        token_multiplier: TokenMultiplierLayout,
+        depeg: DepegLayout,
+        last_amp_updated_timestamp: u64,
+    },
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializePermissionlessPoolLayout {
+    curve_type: CurveTypeLayout,
+    token_a_amount: u64,
+    token_b_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializePermissionlessPoolWithFeeTierLayout {
+    curve_type: CurveTypeLayout,
+    trade_fee_bps: u64,
+    token_a_amount: u64,
+    token_b_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializeWithConfigLayout {
+    token_a_amount: u64,
+    token_b_amount: u64,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializeCustomizableLayout {
+    token_a_amount: u64,
+    token_b_amount: u64,
+    trade_fee_numerator: u32,
+}
+
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<TradeData> {
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
+    let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
+
+    let mut td = TradeData::default();
+    let mut result = None;
+
+    match discriminator {
+        InitializePermissionlessPool => {
+            td.dapp = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB".to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = input_accounts.get(2)?.to_string();
+            td.quote_mint = input_accounts.get(3)?.to_string();
+            let args = InitializePermissionlessPoolLayout::deserialize(&mut rest.clone())
+                .unwrap_or_default();
+            td.initial_base_amount = args.token_a_amount;
+            td.initial_quote_amount = args.token_b_amount;
+            result = Some(td);
+        }
+        InitializePermissionlessPoolWithFeeTier => {
+            td.dapp = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB".to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = input_accounts.get(2)?.to_string();
+            td.quote_mint = input_accounts.get(3)?.to_string();
+            let args =
+                InitializePermissionlessPoolWithFeeTierLayout::deserialize(&mut rest.clone())
+                    .unwrap_or_default();
+            td.initial_base_amount = args.token_a_amount;
+            td.initial_quote_amount = args.token_b_amount;
+            td.fee_numerator = args.trade_fee_bps;
+            td.fee_denominator = BPS_DENOMINATOR;
+            result = Some(td);
+        }
+        InitializePermissionlessConstantProductPoolWithConfig
+        | InitializePermissionlessConstantProductPoolWithConfig2 => {
+            // Fees are read from the config account, not from the instruction.
+            td.dapp = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB".to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = input_accounts.get(3)?.to_string();
+            td.quote_mint = input_accounts.get(4)?.to_string();
+            let args =
+                InitializeWithConfigLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.initial_base_amount = args.token_a_amount;
+            td.initial_quote_amount = args.token_b_amount;
+            result = Some(td);
+        }
+        InitializeCustomizablePermissionlessConstantProductPool => {
+            td.dapp = "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB".to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = input_accounts.get(2)?.to_string();
+            td.quote_mint = input_accounts.get(3)?.to_string();
+            let args =
+                InitializeCustomizableLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.initial_base_amount = args.token_a_amount;
+            td.initial_quote_amount = args.token_b_amount;
+            td.fee_numerator = args.trade_fee_numerator as u64;
+            td.fee_denominator = FEE_DENOMINATOR;
+            result = Some(td);
+        }
+        _ => {}
+    }
+
+    return result;
+}
@@ -1,15 +1,34 @@
+use borsh::BorshDeserialize;
 use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
 
-use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+use crate::pb::sf::solana::dex::pool::creations::v1::{FeeConfig, TradeData};
 
 const InitializeLbPair: u64 = u64::from_le_bytes([45, 154, 237, 210, 221, 15, 166, 92]);
+const InitializePresetParameter: u64 = u64::from_le_bytes([66, 188, 71, 211, 98, 109, 14, 186]);
+
+// The base fee is base_factor * bin_step * 10 in units of 1e-9.
+const FEE_PRECISION: u64 = 1_000_000_000;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitPresetParametersLayout {
+    bin_step: u16,
+    base_factor: u16,
+    filter_period: u16,
+    decay_period: u16,
+    reduction_factor: u16,
+    variable_fee_control: u32,
+    max_volatility_accumulator: u32,
+    min_bin_id: i32,
+    max_bin_id: i32,
+    protocol_share: u16,
+}
 
 pub fn parse_trade_instruction(
     bytes_stream: Vec<u8>,
     input_accounts: Vec<String>,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(8);
-    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let mut td = TradeData::default();
@@ -18,9 +37,10 @@ pub fn parse_trade_instruction(
     match discriminator {
         InitializeLbPair => {
             td.dapp = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.base_mint = input_accounts.get(2).unwrap().to_string();
-            td.quote_mint = input_accounts.get(3).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = input_accounts.get(2)?.to_string();
+            td.quote_mint = input_accounts.get(3)?.to_string();
+            td.fee_config = input_accounts.get(7)?.to_string();
             result = Some(td);
         }
         _ => {}
@@ -28,3 +48,27 @@ pub fn parse_trade_instruction(
 
     return result;
 }
+
+// Pairs take the base fee of the preset parameter they are created from, the variable
+// fee on top of it depends on volatility and isn't known at creation.
+pub fn parse_fee_config_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<FeeConfig> {
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
+    let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
+
+    match discriminator {
+        InitializePresetParameter => {
+            let args = InitPresetParametersLayout::deserialize(&mut rest.clone()).ok()?;
+            Some(FeeConfig {
+                dapp: "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo".to_string(),
+                account: input_accounts.get(0)?.to_string(),
+                fee_numerator: args.base_factor as u64 * args.bin_step as u64 * 10,
+                fee_denominator: FEE_PRECISION,
+            })
+        }
+        _ => None,
+    }
+}
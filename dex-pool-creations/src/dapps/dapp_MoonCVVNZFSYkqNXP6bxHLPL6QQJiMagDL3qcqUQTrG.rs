@@ -8,15 +8,15 @@ pub fn parse_trade_instruction(
     bytes_stream: Vec<u8>,
     input_accounts: Vec<String>,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(8);
-    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let mut td = TradeData::default();
     let mut result = None;
 
     match discriminator {
-        Create => {
+        TokenMint => {
             td.dapp = "MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG".to_string();
             td.pool = input_accounts.get(2).unwrap_or(&"".to_string()).to_string();
             td.associated_account = input_accounts.get(5).unwrap_or(&"".to_string()).to_string();
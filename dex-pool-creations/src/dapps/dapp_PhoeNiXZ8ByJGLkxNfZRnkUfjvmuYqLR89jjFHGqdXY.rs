@@ -0,0 +1,52 @@
+use borsh::BorshDeserialize;
+use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
+
+use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+
+const InitializeMarket: u8 = 100;
+
+const BPS_DENOMINATOR: u64 = 10000;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct MarketSizeParamsLayout {
+    bids_size: u64,
+    asks_size: u64,
+    num_seats: u64,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializeParamsLayout {
+    market_size_params: MarketSizeParamsLayout,
+    num_quote_lots_per_quote_unit: u64,
+    tick_size_in_quote_lots_per_base_unit: u64,
+    num_base_lots_per_base_unit: u64,
+    taker_fee_bps: u16,
+    fee_collector: [u8; 32],
+    raw_base_units_per_base_unit: Option<u32>,
+}
+
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<TradeData> {
+    let (&discriminator, rest) = bytes_stream.split_first()?;
+
+    let mut td = TradeData::default();
+    let mut result = None;
+
+    match discriminator {
+        InitializeMarket => {
+            td.dapp = "PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY".to_string();
+            td.pool = input_accounts.get(2)?.to_string();
+            td.base_mint = input_accounts.get(4)?.to_string();
+            td.quote_mint = input_accounts.get(5)?.to_string();
+            let args = InitializeParamsLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.fee_numerator = args.taker_fee_bps as u64;
+            td.fee_denominator = BPS_DENOMINATOR;
+            result = Some(td);
+        }
+        _ => {}
+    }
+
+    return result;
+}
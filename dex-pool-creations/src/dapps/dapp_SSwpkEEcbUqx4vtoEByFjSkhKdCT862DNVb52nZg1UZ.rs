@@ -0,0 +1,58 @@
+use borsh::BorshDeserialize;
+use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
+
+use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+use crate::utils::get_token_amount;
+
+const Initialize: u8 = 0;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct FeesLayout {
+    admin_trade_fee_numerator: u64,
+    admin_trade_fee_denominator: u64,
+    admin_withdraw_fee_numerator: u64,
+    admin_withdraw_fee_denominator: u64,
+    trade_fee_numerator: u64,
+    trade_fee_denominator: u64,
+    withdraw_fee_numerator: u64,
+    withdraw_fee_denominator: u64,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializeLayout {
+    nonce: u8,
+    amp_factor: u64,
+    fees: FeesLayout,
+}
+
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+    post_token_balances: &Vec<TokenBalance>,
+    accounts: &Vec<String>,
+) -> Option<TradeData> {
+    let (&discriminator, rest) = bytes_stream.split_first()?;
+
+    let mut td = TradeData::default();
+    let mut result = None;
+
+    match discriminator {
+        Initialize => {
+            td.dapp = "SSwpkEEcbUqx4vtoEByFjSkhKdCT862DNVb52nZg1UZ".to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = input_accounts.get(5)?.to_string();
+            td.quote_mint = input_accounts.get(7)?.to_string();
+            td.initial_base_amount =
+                get_token_amount(input_accounts.get(6)?, post_token_balances, accounts);
+            td.initial_quote_amount =
+                get_token_amount(input_accounts.get(8)?, post_token_balances, accounts);
+            let args = InitializeLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.fee_numerator = args.fees.trade_fee_numerator;
+            td.fee_denominator = args.fees.trade_fee_denominator;
+            result = Some(td);
+        }
+        _ => {}
+    }
+
+    return result;
+}
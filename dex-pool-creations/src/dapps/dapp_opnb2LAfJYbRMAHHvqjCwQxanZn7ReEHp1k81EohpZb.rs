@@ -0,0 +1,54 @@
+use borsh::BorshDeserialize;
+use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
+
+use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+
+const CreateMarket: u64 = u64::from_le_bytes([103, 226, 97, 235, 200, 188, 251, 254]);
+
+// OpenBook v2 expresses maker and taker fees in millionths.
+const FEES_SCALE_FACTOR: u64 = 1000000;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct OracleConfigParamsLayout {
+    conf_filter: f32,
+    max_staleness_slots: Option<u32>,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct CreateMarketLayout {
+    name: String,
+    oracle_config: OracleConfigParamsLayout,
+    quote_lot_size: i64,
+    base_lot_size: i64,
+    maker_fee: i64,
+    taker_fee: i64,
+    time_expiry: i64,
+}
+
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<TradeData> {
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
+    let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
+
+    let mut td = TradeData::default();
+    let mut result = None;
+
+    match discriminator {
+        CreateMarket => {
+            td.dapp = "opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb".to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.base_mint = input_accounts.get(8)?.to_string();
+            td.quote_mint = input_accounts.get(9)?.to_string();
+            let args = CreateMarketLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.fee_numerator = args.taker_fee.max(0) as u64;
+            td.fee_denominator = FEES_SCALE_FACTOR;
+            result = Some(td);
+        }
+        _ => {}
+    }
+
+    return result;
+}
@@ -1,16 +1,33 @@
+use borsh::BorshDeserialize;
 use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
 
-use crate::pb::sf::solana::dex::pool::creations::v1::TradeData;
+use crate::pb::sf::solana::dex::pool::creations::v1::{FeeConfig, TradeData};
 
 const InitializePool: u64 = u64::from_le_bytes([95, 180, 10, 172, 84, 174, 232, 40]);
 const InitializePoolV2: u64 = u64::from_le_bytes([207, 45, 87, 242, 27, 63, 204, 67]);
+const InitializeFeeTier: u64 = u64::from_le_bytes([183, 74, 156, 160, 112, 2, 42, 30]);
+const SetDefaultFeeRate: u64 = u64::from_le_bytes([118, 215, 214, 157, 182, 229, 208, 228]);
+
+// Fee rates are in hundredths of a basis point.
+const FEE_RATE_DENOMINATOR: u64 = 1_000_000;
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct InitializeFeeTierLayout {
+    tick_spacing: u16,
+    default_fee_rate: u16,
+}
+
+#[derive(BorshDeserialize, Debug, Default)]
+pub struct SetDefaultFeeRateLayout {
+    default_fee_rate: u16,
+}
 
 pub fn parse_trade_instruction(
     bytes_stream: Vec<u8>,
     input_accounts: Vec<String>,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(8);
-    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let mut td = TradeData::default();
@@ -19,14 +36,18 @@ pub fn parse_trade_instruction(
     match discriminator {
         InitializePool => {
             td.dapp = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string();
-            td.pool = input_accounts.get(4).unwrap().to_string();
-            td.base_mint = input_accounts.get(1).unwrap().to_string();
-            td.quote_mint = input_accounts.get(2).unwrap().to_string();
+            td.pool = input_accounts.get(4)?.to_string();
+            td.base_mint = input_accounts.get(1)?.to_string();
+            td.quote_mint = input_accounts.get(2)?.to_string();
+            td.fee_config = input_accounts.get(7)?.to_string();
             result = Some(td);
         }
         InitializePoolV2 => {
             td.dapp = "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string();
-            td.pool = input_accounts.get(6).unwrap().to_string();
+            td.pool = input_accounts.get(6)?.to_string();
+            td.base_mint = input_accounts.get(1)?.to_string();
+            td.quote_mint = input_accounts.get(2)?.to_string();
+            td.fee_config = input_accounts.get(9)?.to_string();
             result = Some(td);
         }
         _ => {}
@@ -34,3 +55,34 @@ pub fn parse_trade_instruction(
 
     return result;
 }
+
+// Pools start at the default fee rate of their fee tier.
+pub fn parse_fee_config_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<FeeConfig> {
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
+    let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
+
+    let fee_numerator = match discriminator {
+        InitializeFeeTier => {
+            InitializeFeeTierLayout::deserialize(&mut rest.clone())
+                .ok()?
+                .default_fee_rate
+        }
+        SetDefaultFeeRate => {
+            SetDefaultFeeRateLayout::deserialize(&mut rest.clone())
+                .ok()?
+                .default_fee_rate
+        }
+        _ => return None,
+    };
+
+    Some(FeeConfig {
+        dapp: "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc".to_string(),
+        account: input_accounts.get(1)?.to_string(),
+        fee_numerator: fee_numerator as u64,
+        fee_denominator: FEE_RATE_DENOMINATOR,
+    })
+}
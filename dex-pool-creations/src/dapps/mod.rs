@@ -1,7 +1,16 @@
 pub mod dapp_675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8;
 pub mod dapp_6EF8rrecthR5Dkzon8Nwu78hRvfCKubJ14M5uBEwF6P;
+pub mod dapp_9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP;
+pub mod dapp_9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin;
 pub mod dapp_CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK;
 pub mod dapp_CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C;
+pub mod dapp_DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1;
+pub mod dapp_Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB;
 pub mod dapp_LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo;
+pub mod dapp_MERLuDFBMmsHnsBPZw2sDQZHvXFMwp8EdjudcU2HKky;
 pub mod dapp_MoonCVVNZFSYkqNXP6bxHLPL6QQJiMagDL3qcqUQTrG;
+pub mod dapp_PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY;
+pub mod dapp_SSwpkEEcbUqx4vtoEByFjSkhKdCT862DNVb52nZg1UZ;
+pub mod dapp_opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb;
+pub mod dapp_srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX;
 pub mod dapp_whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc;
@@ -6,10 +6,14 @@ mod dapps;
 mod pb;
 mod utils;
 
-use crate::pb::sf::solana::dex::pool::creations::v1::{Output, TradeData};
+use crate::pb::sf::solana::dex::pool::creations::v1::{FeeConfig, Output, TradeData};
 
 use substreams::log;
-use substreams_solana::pb::sf::solana::r#type::v1::{Block, InnerInstructions};
+use substreams::store::{
+    StoreGet, StoreGetProto, StoreNew, StoreSet, StoreSetIfNotExists, StoreSetIfNotExistsProto,
+    StoreSetProto,
+};
+use substreams_solana::pb::sf::solana::r#type::v1::{Block, InnerInstructions, TokenBalance};
 use utils::convert_to_date;
 
 #[substreams::handlers::map]
@@ -17,17 +21,53 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
     process_block(block)
 }
 
+// Latest fee of each config account, updates overwrite the rate set at creation.
+#[substreams::handlers::store]
+fn store_fee_configs(output: Output, store: StoreSetProto<FeeConfig>) {
+    for (ordinal, fee_config) in output.fee_configs.iter().enumerate() {
+        store.set(ordinal as u64, &fee_config.account, fee_config);
+    }
+}
+
+#[substreams::handlers::store]
+fn store_pools(
+    output: Output,
+    fee_configs_store: StoreGetProto<FeeConfig>,
+    store: StoreSetIfNotExistsProto<TradeData>,
+) {
+    for (ordinal, mut pool) in output.data.into_iter().enumerate() {
+        if pool.pool.is_empty() {
+            continue;
+        }
+        // Concentrated liquidity pools don't carry their fee, it comes from the config
+        // account they are created with.
+        if !pool.fee_config.is_empty() {
+            if let Some(fee_config) = fee_configs_store.get_last(&pool.fee_config) {
+                pool.fee_numerator = fee_config.fee_numerator;
+                pool.fee_denominator = fee_config.fee_denominator;
+            }
+        }
+        // The first initialisation seen for a pool is its creation, later ones are ignored.
+        store.set_if_not_exists(ordinal as u64, &pool.pool, &pool);
+    }
+}
+
 fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
     let slot = block.slot;
     let parent_slot = block.parent_slot;
     let timestamp = block.block_time.as_ref();
     let mut data: Vec<TradeData> = vec![];
+    let mut fee_configs: Vec<FeeConfig> = vec![];
     if timestamp.is_some() {
         let timestamp = timestamp.unwrap().timestamp;
         for trx in block.transactions_owned() {
             let accounts = trx.resolved_accounts_as_strings();
             if let Some(transaction) = trx.transaction {
                 let meta = trx.meta.unwrap();
+                // Nothing a failed transaction initialises is ever created.
+                if meta.err.is_some() {
+                    continue;
+                }
 
                 let msg = transaction.message.unwrap();
 
@@ -36,8 +76,20 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
                     let inner_instructions =
                         filter_inner_instructions(&meta.inner_instructions, idx as u32);
 
-                    let trade_data =
-                        get_trade_data(program, inst.data, &inst.accounts, &accounts, timestamp);
+                    if let Some(fee_config) =
+                        get_fee_config(program, &inst.data, &inst.accounts, &accounts)
+                    {
+                        fee_configs.push(fee_config);
+                    }
+
+                    let trade_data = get_trade_data(
+                        program,
+                        inst.data,
+                        &inst.accounts,
+                        &accounts,
+                        &meta.post_token_balances,
+                        timestamp,
+                    );
 
                     if trade_data.is_some() {
                         data.push(trade_data.unwrap());
@@ -47,11 +99,21 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
                         inner_instruction.instructions.iter().enumerate().for_each(
                             |(inner_idx, inner_inst)| {
                                 let inner_program = &accounts[inner_inst.program_id_index as usize];
+                                if let Some(fee_config) = get_fee_config(
+                                    inner_program,
+                                    &inner_inst.data,
+                                    &inner_inst.accounts,
+                                    &accounts,
+                                ) {
+                                    fee_configs.push(fee_config);
+                                }
+
                                 let trade_data = get_trade_data(
                                     inner_program,
                                     inner_inst.data.clone(),
                                     &inner_inst.accounts,
                                     &accounts,
+                                    &meta.post_token_balances,
                                     timestamp,
                                 );
 
@@ -67,7 +129,7 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
     }
 
     log::info!("{:#?}", slot);
-    Ok(Output { data })
+    Ok(Output { data, fee_configs })
 }
 
 fn get_trade_data(
@@ -75,6 +137,7 @@ fn get_trade_data(
     instruction_data: Vec<u8>,
     account_indices: &Vec<u8>,
     accounts: &Vec<String>,
+    post_token_balances: &Vec<TokenBalance>,
     timestamp: i64,
 ) -> Option<TradeData> {
     let input_accounts = prepare_input_accounts(account_indices, accounts);
@@ -131,6 +194,80 @@ fn get_trade_data(
                     input_accounts,
                 );
         }
+        "Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB" => {
+            result =
+                dapps::dapp_Eo7WjKq67rjJQSZxS6z3YkapzY3eMj6Xy8X5EQVn5UaB::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                );
+        }
+        "PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY" => {
+            result =
+                dapps::dapp_PhoeNiXZ8ByJGLkxNfZRnkUfjvmuYqLR89jjFHGqdXY::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                );
+        }
+        "srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX" => {
+            result =
+                dapps::dapp_srmqPvymJeFKQ4zGQed1GFppgkRHL9kaELCbyksJtPX::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                );
+        }
+        "9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin" => {
+            result =
+                dapps::dapp_9xQeWvG816bUx9EPjHmaT23yvVM2ZWbrrpZb9PusVFin::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                );
+        }
+        "opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb" => {
+            result =
+                dapps::dapp_opnb2LAfJYbRMAHHvqjCwQxanZn7ReEHp1k81EohpZb::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                );
+        }
+        "SSwpkEEcbUqx4vtoEByFjSkhKdCT862DNVb52nZg1UZ" => {
+            result =
+                dapps::dapp_SSwpkEEcbUqx4vtoEByFjSkhKdCT862DNVb52nZg1UZ::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                    post_token_balances,
+                    accounts,
+                );
+        }
+        "MERLuDFBMmsHnsBPZw2sDQZHvXFMwp8EdjudcU2HKky" => {
+            result =
+                dapps::dapp_MERLuDFBMmsHnsBPZw2sDQZHvXFMwp8EdjudcU2HKky::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                    post_token_balances,
+                    accounts,
+                );
+        }
+        // Both are deployments of the SPL token swap program.
+        "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP"
+        | "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8" => {
+            result =
+                dapps::dapp_9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP::parse_trade_instruction(
+                    dapp_address,
+                    instruction_data,
+                    input_accounts,
+                    post_token_balances,
+                    accounts,
+                );
+        }
+        "DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1" => {
+            result =
+                dapps::dapp_DjVE6JNiYqPL2QXyCUUh8rNjHrbz9hXHNYt99MQ59qw1::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                    post_token_balances,
+                    accounts,
+                );
+        }
         _ => {}
     }
 
@@ -145,6 +282,44 @@ fn get_trade_data(
     result
 }
 
+fn get_fee_config(
+    dapp_address: &String,
+    instruction_data: &Vec<u8>,
+    account_indices: &Vec<u8>,
+    accounts: &Vec<String>,
+) -> Option<FeeConfig> {
+    let instruction_data = instruction_data.clone();
+    let input_accounts = prepare_input_accounts(account_indices, accounts);
+
+    match dapp_address.as_str() {
+        "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc" => {
+            dapps::dapp_whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc::parse_fee_config_instruction(
+                instruction_data,
+                input_accounts,
+            )
+        }
+        "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK" => {
+            dapps::dapp_CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK::parse_fee_config_instruction(
+                instruction_data,
+                input_accounts,
+            )
+        }
+        "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo" => {
+            dapps::dapp_LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo::parse_fee_config_instruction(
+                instruction_data,
+                input_accounts,
+            )
+        }
+        "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C" => {
+            dapps::dapp_CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C::parse_fee_config_instruction(
+                instruction_data,
+                input_accounts,
+            )
+        }
+        _ => None,
+    }
+}
+
 fn prepare_input_accounts(account_indices: &Vec<u8>, accounts: &Vec<String>) -> Vec<String> {
     let mut instruction_accounts: Vec<String> = vec![];
     for (index, &el) in account_indices.iter().enumerate() {
@@ -4,6 +4,8 @@
 pub struct Output {
     #[prost(message, repeated, tag="1")]
     pub data: ::prost::alloc::vec::Vec<TradeData>,
+    #[prost(message, repeated, tag="2")]
+    pub fee_configs: ::prost::alloc::vec::Vec<FeeConfig>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -24,5 +26,27 @@ pub struct TradeData {
     pub base_mint: ::prost::alloc::string::String,
     #[prost(string, required, tag="8")]
     pub quote_mint: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="9")]
+    pub initial_base_amount: u64,
+    #[prost(uint64, required, tag="10")]
+    pub initial_quote_amount: u64,
+    #[prost(uint64, required, tag="11")]
+    pub fee_numerator: u64,
+    #[prost(uint64, required, tag="12")]
+    pub fee_denominator: u64,
+    #[prost(string, required, tag="13")]
+    pub fee_config: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeeConfig {
+    #[prost(string, required, tag="1")]
+    pub dapp: ::prost::alloc::string::String,
+    #[prost(string, required, tag="2")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="3")]
+    pub fee_numerator: u64,
+    #[prost(uint64, required, tag="4")]
+    pub fee_denominator: u64,
 }
 // @@protoc_insertion_point(module)
@@ -11,3 +11,47 @@ pub fn convert_to_date(ts: i64) -> String {
     let res = dt.format("%Y-%m-%d");
     return res.to_string();
 }
+
+pub fn get_mint(
+    address: &String,
+    token_balances: &Vec<TokenBalance>,
+    accounts: &Vec<String>,
+) -> String {
+    let mut result: String = String::new();
+    let index = match accounts.iter().position(|r| r == address) {
+        Some(index) => index,
+        None => return result,
+    };
+
+    token_balances
+        .iter()
+        .filter(|token_balance| token_balance.account_index == index as u32)
+        .for_each(|token_balance| {
+            result = token_balance.mint.clone();
+        });
+    return result;
+}
+
+pub fn get_token_amount(
+    address: &String,
+    token_balances: &Vec<TokenBalance>,
+    accounts: &Vec<String>,
+) -> u64 {
+    let mut result: u64 = 0;
+    let index = match accounts.iter().position(|r| r == address) {
+        Some(index) => index,
+        None => return result,
+    };
+
+    token_balances
+        .iter()
+        .filter(|token_balance| token_balance.account_index == index as u32)
+        .for_each(|token_balance| {
+            result = token_balance
+                .ui_token_amount
+                .as_ref()
+                .map(|ui_token_amount| ui_token_amount.amount.parse::<u64>().unwrap_or(0))
+                .unwrap_or(0);
+        });
+    return result;
+}
@@ -6,8 +6,10 @@ mod dapps;
 mod pb;
 mod utils;
 
+use pb::sf::solana::dex::pool::creations::v1::TradeData as PoolCreation;
 use pb::sf::solana::dex::trades::v1::{Output, TradeData};
 use substreams::log;
+use substreams::store::{StoreGet, StoreGetProto};
 use substreams_solana::pb::sf::solana::r#type::v1::InnerInstructions;
 use substreams_solana::pb::sf::solana::r#type::v1::{Block, TokenBalance};
 use utils::get_mint;
@@ -15,11 +17,17 @@ use utils::{convert_to_date, get_amt};
 mod trade_instruction;
 
 #[substreams::handlers::map]
-fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
-    process_block(block)
+fn map_block(
+    block: Block,
+    pool_store: StoreGetProto<PoolCreation>,
+) -> Result<Output, substreams::errors::Error> {
+    process_block(block, pool_store)
 }
 
-fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
+fn process_block(
+    block: Block,
+    pool_store: StoreGetProto<PoolCreation>,
+) -> Result<Output, substreams::errors::Error> {
     let slot = block.slot;
     let parent_slot = block.parent_slot;
     let timestamp = block.block_time.as_ref();
@@ -114,6 +122,7 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
                                 &pre_balances,
                                 &post_balances,
                             ),
+                            ..Default::default()
                         });
 
                         if td.second_swap_amm.clone().unwrap_or_default() != "" {
@@ -171,6 +180,7 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
                                     &pre_balances,
                                     &post_balances,
                                 ),
+                                ..Default::default()
                             });
                         }
                     }
@@ -257,6 +267,7 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
                                                 &pre_balances,
                                                 &post_balances,
                                             ),
+                                            ..Default::default()
                                         });
 
                                         if inner_td.second_swap_amm.clone().unwrap_or_default()
@@ -326,6 +337,7 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
                                                     &pre_balances,
                                                     &post_balances,
                                                 ),
+                                                ..Default::default()
                                             });
                                         }
                                     }
@@ -337,6 +349,9 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
         }
     }
 
+    data.iter_mut()
+        .for_each(|trade_data| apply_pool_registry(trade_data, &pool_store));
+
     log::info!("{:#?}", slot);
     Ok(Output { data })
 }
@@ -760,6 +775,30 @@ fn get_trade_instruction(
     return result;
 }
 
+fn apply_pool_registry(trade_data: &mut TradeData, pool_store: &StoreGetProto<PoolCreation>) {
+    let pool = match pool_store.get_last(&trade_data.pool_address) {
+        Some(pool) => pool,
+        None => return,
+    };
+
+    trade_data.pool_created_at = pool.block_time;
+    trade_data.pool_creator = pool.signer;
+
+    if pool.base_mint.is_empty() || pool.quote_mint.is_empty() {
+        return;
+    }
+
+    // The registry knows both mints even when a vault is missing from post_token_balances,
+    // the mints resolved from the vaults are only used to keep the swap orientation.
+    if trade_data.base_mint == pool.quote_mint || trade_data.quote_mint == pool.base_mint {
+        trade_data.base_mint = pool.quote_mint;
+        trade_data.quote_mint = pool.base_mint;
+    } else {
+        trade_data.base_mint = pool.base_mint;
+        trade_data.quote_mint = pool.quote_mint;
+    }
+}
+
 fn get_signer_balance_change(pre_balances: &Vec<u64>, post_balances: &Vec<u64>) -> i64 {
     return (post_balances[0] - pre_balances[0]) as i64;
 }
@@ -2,6 +2,15 @@
 pub mod sf {
     pub mod solana {
         pub mod dex {
+            pub mod pool {
+                pub mod creations {
+                    // @@protoc_insertion_point(attribute:sf.solana.dex.pool.creations.v1)
+                    pub mod v1 {
+                        include!("../../../dex-pool-creations/src/pb/sf.solana.dex.pool.creations.v1.rs");
+                        // @@protoc_insertion_point(sf.solana.dex.pool.creations.v1)
+                    }
+                }
+            }
             pub mod trades {
                 // @@protoc_insertion_point(attribute:sf.solana.dex.trades.v1)
                 pub mod v1 {
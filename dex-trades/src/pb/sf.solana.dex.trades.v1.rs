@@ -48,5 +48,9 @@ pub struct TradeData {
     pub txn_fee_lamports: u64,
     #[prost(int64, required, tag="20")]
     pub signer_lamports_change: i64,
+    #[prost(int64, required, tag="21")]
+    pub pool_created_at: i64,
+    #[prost(string, required, tag="22")]
+    pub pool_creator: ::prost::alloc::string::String,
 }
 // @@protoc_insertion_point(module)
@@ -0,0 +1,3 @@
+// Mainnet-beta has no warmup epochs, every epoch is the same number of slots starting
+// from genesis.
+pub const SLOTS_PER_EPOCH: u64 = 432_000;
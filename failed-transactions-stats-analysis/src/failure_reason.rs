@@ -0,0 +1,261 @@
+use log_tree_parser::LogContext;
+use substreams_solana::pb::sf::solana::r#type::v1::Message;
+
+use crate::pb::sf::solana::transaction_stats::v1::{Error, FailureReason};
+
+const SLIPPAGE_EXCEEDED: &str = "slippage_exceeded";
+const INSUFFICIENT_FUNDS: &str = "insufficient_funds";
+const BLOCKHASH_EXPIRED: &str = "blockhash_expired";
+const COMPUTE_EXHAUSTED: &str = "compute_exhausted";
+const ACCOUNT_IN_USE: &str = "account_in_use";
+const CUSTOM_PROGRAM_ERROR: &str = "custom_program_error";
+const INSTRUCTION_ERROR: &str = "instruction_error";
+const OTHER: &str = "other";
+
+// Variants of the bincode serialized `TransactionError` in the transaction meta.
+const TRANSACTION_ERRORS: [&str; 37] = [
+    "AccountInUse",
+    "AccountLoadedTwice",
+    "AccountNotFound",
+    "ProgramAccountNotFound",
+    "InsufficientFundsForFee",
+    "InvalidAccountForFee",
+    "AlreadyProcessed",
+    "BlockhashNotFound",
+    "InstructionError",
+    "CallChainTooDeep",
+    "MissingSignatureForFee",
+    "InvalidAccountIndex",
+    "SignatureFailure",
+    "InvalidProgramForExecution",
+    "SanitizeFailure",
+    "ClusterMaintenance",
+    "AccountBorrowOutstanding",
+    "WouldExceedMaxBlockCostLimit",
+    "UnsupportedVersion",
+    "InvalidWritableAccount",
+    "WouldExceedMaxAccountCostLimit",
+    "WouldExceedAccountDataBlockLimit",
+    "TooManyAccountLocks",
+    "AddressLookupTableNotFound",
+    "InvalidAddressLookupTableOwner",
+    "InvalidAddressLookupTableData",
+    "InvalidAddressLookupTableIndex",
+    "InvalidRentPayingAccount",
+    "WouldExceedMaxVoteCostLimit",
+    "WouldExceedAccountDataTotalLimit",
+    "DuplicateInstruction",
+    "InsufficientFundsForRent",
+    "MaxLoadedAccountsDataSizeExceeded",
+    "InvalidLoadedAccountsDataSizeLimit",
+    "ResanitizationNeeded",
+    "ProgramExecutionTemporarilyRestricted",
+    "UnbalancedTransaction",
+];
+const ACCOUNT_IN_USE_ERROR: u32 = 0;
+const ACCOUNT_LOADED_TWICE_ERROR: u32 = 1;
+const INSUFFICIENT_FUNDS_FOR_FEE_ERROR: u32 = 4;
+const ALREADY_PROCESSED_ERROR: u32 = 6;
+const BLOCKHASH_NOT_FOUND_ERROR: u32 = 7;
+const INSTRUCTION_ERROR_ERROR: u32 = 8;
+const MAX_BLOCK_COST_LIMIT_ERROR: u32 = 17;
+const MAX_ACCOUNT_COST_LIMIT_ERROR: u32 = 20;
+const TOO_MANY_ACCOUNT_LOCKS_ERROR: u32 = 22;
+const INSUFFICIENT_FUNDS_FOR_RENT_ERROR: u32 = 31;
+
+// Variants of the `InstructionError` wrapped by `TransactionError::InstructionError`.
+const INSTRUCTION_ERRORS: [&str; 54] = [
+    "GenericError",
+    "InvalidArgument",
+    "InvalidInstructionData",
+    "InvalidAccountData",
+    "AccountDataTooSmall",
+    "InsufficientFunds",
+    "IncorrectProgramId",
+    "MissingRequiredSignature",
+    "AccountAlreadyInitialized",
+    "UninitializedAccount",
+    "UnbalancedInstruction",
+    "ModifiedProgramId",
+    "ExternalAccountLamportSpend",
+    "ExternalAccountDataModified",
+    "ReadonlyLamportChange",
+    "ReadonlyDataModified",
+    "DuplicateAccountIndex",
+    "ExecutableModified",
+    "RentEpochModified",
+    "NotEnoughAccountKeys",
+    "AccountDataSizeChanged",
+    "AccountNotExecutable",
+    "AccountBorrowFailed",
+    "AccountBorrowOutstanding",
+    "DuplicateAccountOutOfSync",
+    "Custom",
+    "InvalidError",
+    "ExecutableDataModified",
+    "ExecutableLamportChange",
+    "ExecutableAccountNotRentExempt",
+    "UnsupportedProgramId",
+    "CallDepth",
+    "MissingAccount",
+    "ReentrancyNotAllowed",
+    "MaxSeedLengthExceeded",
+    "InvalidSeeds",
+    "InvalidRealloc",
+    "ComputationalBudgetExceeded",
+    "PrivilegeEscalation",
+    "ProgramEnvironmentSetupFailure",
+    "ProgramFailedToComplete",
+    "ProgramFailedToCompile",
+    "Immutable",
+    "IncorrectAuthority",
+    "BorshIoError",
+    "AccountNotRentExempt",
+    "InvalidAccountOwner",
+    "ArithmeticOverflow",
+    "UnsupportedSysvar",
+    "IllegalOwner",
+    "MaxAccountsDataAllocationsExceeded",
+    "MaxAccountsResizesExceeded",
+    "MaxInstructionTraceLengthExceeded",
+    "BuiltinProgramsMustConsumeComputeUnits",
+];
+const INSUFFICIENT_FUNDS_INSTRUCTION_ERROR: u32 = 5;
+const CUSTOM_INSTRUCTION_ERROR: u32 = 25;
+const COMPUTATIONAL_BUDGET_EXCEEDED_INSTRUCTION_ERROR: u32 = 37;
+
+// SPL Token's InsufficientFunds and the System program's ResultWithNegativeLamports.
+const INSUFFICIENT_FUNDS_PROGRAMS: [&str; 3] = [
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    "11111111111111111111111111111111",
+];
+const INSUFFICIENT_FUNDS_CODE: u32 = 1;
+
+// Minimum out / maximum in checks of the swap programs in the error registry whose
+// names don't mention slippage.
+const SLIPPAGE_ERRORS: [&str; 7] = [
+    "AmountOutBelowMinimum",
+    "AmountInAboveMaximum",
+    "TooLittleOutputReceived",
+    "TooMuchInputPaid",
+    "TokenMinSubceeded",
+    "TokenMaxExceeded",
+    "InsufficientOutAmount",
+];
+
+const COMPUTE_EXHAUSTED_MESSAGE: &str = "exceeded CUs meter";
+
+pub fn get_failure_reason(
+    err: &[u8],
+    accounts: &Vec<String>,
+    message: &Message,
+    error: Option<&Error>,
+    failing_invocation: Option<&LogContext>,
+) -> FailureReason {
+    let mut failure_reason = FailureReason::default();
+    failure_reason.failing_program = error.map(|error| error.program.to_string());
+
+    let variant = match read_u32(err, 0) {
+        Some(variant) => variant,
+        None => {
+            failure_reason.category = OTHER.to_string();
+            return failure_reason;
+        }
+    };
+    failure_reason.transaction_error = TRANSACTION_ERRORS
+        .get(variant as usize)
+        .unwrap_or(&"Unknown")
+        .to_string();
+
+    let category = match variant {
+        ACCOUNT_IN_USE_ERROR | ACCOUNT_LOADED_TWICE_ERROR | TOO_MANY_ACCOUNT_LOCKS_ERROR => {
+            ACCOUNT_IN_USE
+        }
+        INSUFFICIENT_FUNDS_FOR_FEE_ERROR | INSUFFICIENT_FUNDS_FOR_RENT_ERROR => INSUFFICIENT_FUNDS,
+        ALREADY_PROCESSED_ERROR | BLOCKHASH_NOT_FOUND_ERROR => BLOCKHASH_EXPIRED,
+        MAX_BLOCK_COST_LIMIT_ERROR | MAX_ACCOUNT_COST_LIMIT_ERROR => COMPUTE_EXHAUSTED,
+        INSTRUCTION_ERROR_ERROR => {
+            // InstructionError(u8, InstructionError), Custom carries a u32 code.
+            let instruction_index = err.get(4).copied();
+            let instruction_error = read_u32(err, 5);
+            let custom_code = match instruction_error {
+                Some(CUSTOM_INSTRUCTION_ERROR) => read_u32(err, 9),
+                _ => None,
+            };
+
+            failure_reason.instruction_index = instruction_index.map(u32::from);
+            failure_reason.instruction_program = instruction_index
+                .and_then(|index| message.instructions.get(index as usize))
+                .map(|inst| accounts[inst.program_id_index as usize].to_string());
+            failure_reason.instruction_error = match (instruction_error, custom_code) {
+                (_, Some(code)) => Some(format!("Custom({})", code)),
+                (Some(instruction_error), None) => INSTRUCTION_ERRORS
+                    .get(instruction_error as usize)
+                    .map(|name| name.to_string()),
+                (None, None) => None,
+            };
+
+            get_instruction_error_category(
+                instruction_error,
+                custom_code,
+                error,
+                failing_invocation,
+            )
+        }
+        _ => OTHER,
+    };
+    failure_reason.category = category.to_string();
+
+    failure_reason
+}
+
+fn get_instruction_error_category(
+    instruction_error: Option<u32>,
+    custom_code: Option<u32>,
+    error: Option<&Error>,
+    failing_invocation: Option<&LogContext>,
+) -> &'static str {
+    let failure_message = error.map_or("", |error| error.message.as_str());
+    if instruction_error == Some(COMPUTATIONAL_BUDGET_EXCEEDED_INSTRUCTION_ERROR)
+        || failure_message.contains(COMPUTE_EXHAUSTED_MESSAGE)
+    {
+        return COMPUTE_EXHAUSTED;
+    }
+    if instruction_error == Some(INSUFFICIENT_FUNDS_INSTRUCTION_ERROR) {
+        return INSUFFICIENT_FUNDS;
+    }
+
+    let code = match custom_code {
+        Some(code) => code,
+        None => return INSTRUCTION_ERROR,
+    };
+    let program = error.map_or("", |error| error.program.as_str());
+    if code == INSUFFICIENT_FUNDS_CODE && INSUFFICIENT_FUNDS_PROGRAMS.contains(&program) {
+        return INSUFFICIENT_FUNDS;
+    }
+
+    // Programs outside the registry usually say so in their own logs.
+    let error_name = error
+        .and_then(|error| error.name.as_ref())
+        .map_or("", |name| name.as_str());
+    let logs_slippage = failing_invocation.map_or(false, |invocation| {
+        invocation
+            .program_logs
+            .iter()
+            .any(|log| log.to_lowercase().contains("slippage"))
+    });
+    if error_name.to_lowercase().contains("slippage")
+        || SLIPPAGE_ERRORS.contains(&error_name)
+        || logs_slippage
+    {
+        return SLIPPAGE_EXCEEDED;
+    }
+
+    CUSTOM_PROGRAM_ERROR
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
@@ -1,3 +1,4 @@
+mod failure_reason;
 mod pb;
 mod utils;
 use std::cmp;
@@ -5,17 +6,17 @@ use std::cmp;
 use std::{collections::HashSet, io::Read};
 
 use bytes::Buf;
-use pb::sf::solana::transaction_stats::v1::{Instruction, Output, TransactionStats};
+use failure_reason::get_failure_reason;
+use pb::sf::solana::transaction_stats::v1::{Error, Instruction, Output, TransactionStats};
 
+use log_tree_parser::{parse_logs, LogContext, LogContextIterator};
+use program_errors::{decode_error, parse_error_code};
 use substreams_solana::pb::sf::solana::r#type::v1::{
     Block, CompiledInstruction, InnerInstructions, Message, MessageHeader, Transaction,
     TransactionStatusMeta,
 };
 use substreams_solana_core::base58;
-use utils::{
-    calculate_byte_size, calculate_instruction_size, compact_array_size, convert_to_date,
-    parse_logs, LogContext,
-};
+use utils::{calculate_byte_size, calculate_instruction_size, compact_array_size, convert_to_date};
 
 const VOTE_ACCOUNT: &str = "Vote111111111111111111111111111111111111111";
 
@@ -126,6 +127,39 @@ fn populate_transaction_stats(
         meta,
     );
     update_transaction_stats_instructions(transaction_stats, accounts, meta, message, parsed_logs);
+
+    let failing_invocation = find_failing_invocation(parsed_logs);
+    transaction_stats.error = failing_invocation.and_then(get_error);
+    transaction_stats.failure_reason = meta.err.as_ref().map(|err| {
+        get_failure_reason(
+            &err.err,
+            accounts,
+            message,
+            transaction_stats.error.as_ref(),
+            failing_invocation,
+        )
+    });
+}
+
+// A failure is logged again by every caller up the invocation chain, the deepest failing
+// invocation is the one the error comes from.
+fn find_failing_invocation(parsed_logs: &Vec<LogContext>) -> Option<&LogContext> {
+    LogContextIterator::new(parsed_logs)
+        .filter(|log_context| log_context.failure_message.is_some())
+        .last()
+}
+
+fn get_error(log_context: &LogContext) -> Option<Error> {
+    let message = log_context.failure_message.as_ref()?;
+    let program_error = decode_error(&log_context.program_id, message);
+
+    Some(Error {
+        program: log_context.program_id.to_string(),
+        message: message.to_string(),
+        code: parse_error_code(message),
+        name: program_error.map(|program_error| program_error.name.to_string()),
+        description: program_error.map(|program_error| program_error.message.to_string()),
+    })
 }
 
 fn process_instruction(
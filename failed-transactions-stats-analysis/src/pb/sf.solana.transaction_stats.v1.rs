@@ -47,6 +47,40 @@ pub struct TransactionStats {
     pub signatures_size: u32,
     #[prost(string, required, tag="20")]
     pub signer: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="21")]
+    pub error: ::core::option::Option<Error>,
+    #[prost(message, optional, tag="22")]
+    pub failure_reason: ::core::option::Option<FailureReason>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Error {
+    #[prost(string, required, tag="1")]
+    pub program: ::prost::alloc::string::String,
+    #[prost(string, required, tag="2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag="3")]
+    pub code: ::core::option::Option<u32>,
+    #[prost(string, optional, tag="4")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="5")]
+    pub description: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FailureReason {
+    #[prost(string, required, tag="1")]
+    pub category: ::prost::alloc::string::String,
+    #[prost(string, required, tag="2")]
+    pub transaction_error: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag="3")]
+    pub instruction_index: ::core::option::Option<u32>,
+    #[prost(string, optional, tag="4")]
+    pub instruction_program: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="5")]
+    pub instruction_error: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="6")]
+    pub failing_program: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
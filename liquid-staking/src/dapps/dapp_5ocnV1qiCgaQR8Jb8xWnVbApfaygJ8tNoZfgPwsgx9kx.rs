@@ -0,0 +1,229 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use substreams_solana::pb::sf::solana::r#type::v1::InnerInstructions;
+
+use crate::{pb::sf::solana::liquid::staking::v1::TradeData, utils::prepare_input_accounts};
+
+const TOKEN_PROGRAMS: [&str; 2] = [
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+];
+
+const SWAP_EXACT_IN_DISCRIMINATOR: u8 = 1;
+const SWAP_EXACT_OUT_DISCRIMINATOR: u8 = 2;
+const ADD_LIQUIDITY_DISCRIMINATOR: u8 = 3;
+const REMOVE_LIQUIDITY_DISCRIMINATOR: u8 = 4;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct SwapLayout {
+    src_lst_value_calc_accs: u8,
+    dst_lst_value_calc_accs: u8,
+    src_lst_index: u32,
+    dst_lst_index: u32,
+    limit_amount: u64,
+    amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct LiquidityLayout {
+    lst_value_calc_accs: u8,
+    lst_index: u32,
+    amount: u64,
+    limit_amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct AmountLayout {
+    amount: u64,
+}
+
+// Token movements of the instruction as (discriminator, accounts, amount), checked
+// variants are folded into their plain counterpart.
+fn get_token_instructions(
+    accounts: &Vec<String>,
+    inner_instructions: &Vec<InnerInstructions>,
+) -> Vec<(u8, Vec<String>, f64)> {
+    inner_instructions
+        .iter()
+        .flat_map(|inner_instruction| inner_instruction.instructions.iter())
+        .filter(|inner_inst| {
+            TOKEN_PROGRAMS.contains(&accounts[inner_inst.program_id_index as usize].as_str())
+                && inner_inst.data.len() >= 9
+        })
+        .filter_map(|inner_inst| {
+            let mut input_accounts = prepare_input_accounts(&inner_inst.accounts, accounts);
+            let discriminator = match inner_inst.data[0] {
+                12 => {
+                    // TransferChecked passes the mint between source and destination.
+                    if input_accounts.len() > 1 {
+                        input_accounts.remove(1);
+                    }
+                    3
+                }
+                14 => 7,
+                15 => 8,
+                discriminator => discriminator,
+            };
+            let data = AmountLayout::deserialize(&mut &inner_inst.data[1..9]).ok()?;
+            Some((discriminator, input_accounts, data.amount as f64))
+        })
+        .collect()
+}
+
+fn get_transfer_amount(
+    token_instructions: &Vec<(u8, Vec<String>, f64)>,
+    source: Option<&String>,
+    destination: Option<&String>,
+) -> f64 {
+    token_instructions
+        .iter()
+        .filter(|(discriminator, input_accounts, _)| {
+            *discriminator == 3
+                && source.map_or(true, |source| input_accounts.get(0) == Some(source))
+                && destination.map_or(true, |destination| {
+                    input_accounts.get(1) == Some(destination)
+                })
+        })
+        .map(|(_, _, amount)| amount)
+        .sum()
+}
+
+fn get_lp_amount(
+    token_instructions: &Vec<(u8, Vec<String>, f64)>,
+    discriminator: u8,
+    lp_mint: &String,
+) -> f64 {
+    // MintTo passes the mint first, Burn passes the burnt account first.
+    let mint_index = if discriminator == 7 { 0 } else { 1 };
+    token_instructions
+        .iter()
+        .filter(|(other, input_accounts, _)| {
+            *other == discriminator && input_accounts.get(mint_index) == Some(lp_mint)
+        })
+        .map(|(_, _, amount)| amount)
+        .sum()
+}
+
+fn enrich_with_ix_data(
+    trade_data: &mut TradeData,
+    outer_idx: u32,
+    inner_idx: u32,
+    is_inner_instruction: bool,
+    outer_program: &String,
+    inner_program: &String,
+) {
+    trade_data.is_inner_instruction = is_inner_instruction;
+    trade_data.instruction_index = outer_idx;
+    trade_data.outer_program = outer_program.to_string();
+    trade_data.inner_instruction_index = inner_idx;
+    trade_data.inner_program = inner_program.to_string();
+}
+
+// Infinity holds many LSTs behind one LP token, rows are tagged with the LST that
+// entered or left the pool and amounts are in that LST's raw units.
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+    accounts: &Vec<String>,
+    inner_instructions: &Vec<InnerInstructions>,
+    outer_program: &String,
+    inner_program: &String,
+    outer_idx: usize,
+    inner_idx: usize,
+    is_inner_instruction: bool,
+) -> Option<TradeData> {
+    let (disc_bytes, rest) = bytes_stream.split_first()?;
+    let token_instructions = get_token_instructions(accounts, inner_instructions);
+
+    let mut trade_data = TradeData::default();
+    match *disc_bytes {
+        SWAP_EXACT_IN_DISCRIMINATOR | SWAP_EXACT_OUT_DISCRIMINATOR => {
+            let data = SwapLayout::deserialize(&mut rest.clone()).ok()?;
+
+            trade_data.lst_mint = input_accounts.get(1)?.to_string();
+            trade_data.dst_lst_mint = input_accounts.get(2)?.to_string();
+            trade_data.fee_account = input_accounts.get(5)?.to_string();
+            trade_data.stake_pool = input_accounts.get(8)?.to_string();
+            trade_data.reserve_stake = input_accounts.get(10)?.to_string();
+            let src_lst_acc = input_accounts.get(3)?;
+            let dst_lst_acc = input_accounts.get(4)?;
+            let dst_pool_reserves = input_accounts.get(11)?;
+
+            if *disc_bytes == SWAP_EXACT_IN_DISCRIMINATOR {
+                trade_data.instruction_type = "SwapExactIn".to_string();
+                trade_data.amount = data.amount as f64;
+                trade_data.dst_amount = get_transfer_amount(
+                    &token_instructions,
+                    Some(dst_pool_reserves),
+                    Some(dst_lst_acc),
+                );
+            } else {
+                trade_data.instruction_type = "SwapExactOut".to_string();
+                trade_data.amount = get_transfer_amount(
+                    &token_instructions,
+                    Some(src_lst_acc),
+                    Some(&trade_data.reserve_stake),
+                );
+                trade_data.dst_amount = data.amount as f64;
+            }
+            trade_data.fee_amount = get_transfer_amount(
+                &token_instructions,
+                Some(dst_pool_reserves),
+                Some(&trade_data.fee_account),
+            );
+        }
+        ADD_LIQUIDITY_DISCRIMINATOR => {
+            let data = LiquidityLayout::deserialize(&mut rest.clone()).ok()?;
+            trade_data.instruction_type = "AddLiquidity".to_string();
+
+            trade_data.lst_mint = input_accounts.get(1)?.to_string();
+            trade_data.pool_mint = input_accounts.get(4)?.to_string();
+            trade_data.fee_account = input_accounts.get(5)?.to_string();
+            trade_data.stake_pool = input_accounts.get(8)?.to_string();
+            trade_data.reserve_stake = input_accounts.get(10)?.to_string();
+
+            trade_data.amount = data.amount as f64;
+            trade_data.mint_amount = get_lp_amount(&token_instructions, 7, &trade_data.pool_mint);
+            trade_data.fee_amount = get_transfer_amount(
+                &token_instructions,
+                Some(&trade_data.reserve_stake),
+                Some(&trade_data.fee_account),
+            );
+        }
+        REMOVE_LIQUIDITY_DISCRIMINATOR => {
+            let data = LiquidityLayout::deserialize(&mut rest.clone()).ok()?;
+            trade_data.instruction_type = "RemoveLiquidity".to_string();
+
+            trade_data.lst_mint = input_accounts.get(1)?.to_string();
+            trade_data.pool_mint = input_accounts.get(4)?.to_string();
+            trade_data.fee_account = input_accounts.get(5)?.to_string();
+            trade_data.stake_pool = input_accounts.get(8)?.to_string();
+            trade_data.reserve_stake = input_accounts.get(10)?.to_string();
+            let dst_lst_acc = input_accounts.get(2)?;
+
+            trade_data.burn_amount = data.amount as f64;
+            trade_data.fee_amount = get_transfer_amount(
+                &token_instructions,
+                Some(&trade_data.reserve_stake),
+                Some(&trade_data.fee_account),
+            );
+            trade_data.amount = -1.0
+                * get_transfer_amount(
+                    &token_instructions,
+                    Some(&trade_data.reserve_stake),
+                    Some(dst_lst_acc),
+                );
+        }
+        _ => return None,
+    }
+
+    enrich_with_ix_data(
+        &mut trade_data,
+        outer_idx as u32,
+        inner_idx as u32,
+        is_inner_instruction,
+        outer_program,
+        inner_program,
+    );
+
+    Some(trade_data)
+}
@@ -3,6 +3,8 @@ use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBala
 
 use crate::{pb::sf::solana::liquid::staking::v1::TradeData, utils::prepare_input_accounts};
 
+const MSOL_MINT: &str = "mSoLzYCxHdYgdzU16g5QSh3i5K3z3KZK7ytfqcJm7So";
+
 const DEPOSIT_DISCRIMINATOR: u64 = 13182846803881894898;
 const DEPOSIT_STAKE_ACCOUNT_DISCRIMINATOR: u64 = 4252073853447275118;
 const CLAIM_DISCRIMINATOR: u64 = 15162669785878545982;
@@ -11,6 +13,9 @@ const UPDATE_DEACTIVATED_DISCRIMINATOR: u64 = 3670262844445943824;
 const UPDATE_ACTIVE_DISCRIMINATOR: u64 = 10979201432142562052;
 const LIQUID_UNSTAKE_DISCRIMINATOR: u64 = 1156549617839971870;
 
+// Rent-exempt reserve of a stake account, which Marinade does not count as stake.
+const STAKE_ACCOUNT_RENT_EXEMPT_RESERVE: u64 = 2_282_880;
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
 struct MintToLayout {
     amount: u64,
@@ -172,6 +177,35 @@ pub fn get_system_sol_transfer(
     result
 }
 
+// SOL of a deposit that reaches the reserve, the rest buys mSOL from the liquidity pool's
+// mSOL leg and is not staked.
+pub fn get_reserve_sol_transfer(
+    trade_data: &TradeData,
+    accounts: &Vec<String>,
+    inner_instructions: &Vec<InnerInstructions>,
+    outer_idx: usize,
+) -> f64 {
+    inner_instructions
+        .iter()
+        .filter(|inner_instruction| inner_instruction.index as usize == outer_idx)
+        .flat_map(|inner_instruction| inner_instruction.instructions.iter())
+        .filter(|inner_inst| {
+            accounts[inner_inst.program_id_index as usize] == "11111111111111111111111111111111"
+        })
+        .filter_map(|inner_inst| {
+            let discriminator = inner_inst.data.get(0..4)?;
+            let amount = inner_inst.data.get(4..12)?;
+            let destination = accounts.get(*inner_inst.accounts.get(1)? as usize)?;
+            if u32::from_le_bytes(discriminator.try_into().ok()?) != 2
+                || *destination != trade_data.reserve_stake
+            {
+                return None;
+            }
+            Some(u64::from_le_bytes(amount.try_into().ok()?) as f64)
+        })
+        .sum()
+}
+
 pub fn get_spl_token_transfer(
     trade_data: &mut TradeData,
     accounts: &Vec<String>,
@@ -229,8 +263,8 @@ pub fn parse_trade_instruction(
     inner_idx: usize,
     is_inner_instruction: bool,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(8);
-    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let mut result = None;
@@ -258,6 +292,8 @@ pub fn parse_trade_instruction(
 
             trade_data.amount =
                 get_system_sol_transfer(&mut trade_data, accounts, inner_instructions);
+            trade_data.staked_amount =
+                get_reserve_sol_transfer(&trade_data, accounts, inner_instructions, outer_idx);
 
             enrich_with_ix_details(
                 &mut trade_data,
@@ -274,11 +310,11 @@ pub fn parse_trade_instruction(
             trade_data = TradeData::default();
             trade_data.instruction_type = "DepositStakeAccount".to_string();
 
-            trade_data.stake_pool = input_accounts.get(0).unwrap().to_string();
+            trade_data.stake_pool = input_accounts.get(0)?.to_string();
             trade_data.withdraw_authority = "".to_string();
             trade_data.reserve_stake = "".to_string();
             trade_data.validator_stake = "".to_string();
-            trade_data.pool_mint = input_accounts.get(7).unwrap().to_string();
+            trade_data.pool_mint = input_accounts.get(7)?.to_string();
             trade_data.fee_account = "".to_string();
 
             trade_data.staking_reward = 0.0;
@@ -286,6 +322,8 @@ pub fn parse_trade_instruction(
             enrich_with_inner_instructions_data(&mut trade_data, accounts, inner_instructions);
             trade_data.fee_amount = 0.0;
             trade_data.amount = 0.0;
+            trade_data.staked_amount =
+                get_stake_account_lamports(input_accounts.get(1)?, accounts, pre_balances);
 
             enrich_with_ix_details(
                 &mut trade_data,
@@ -459,6 +497,10 @@ pub fn parse_trade_instruction(
         _ => {}
     }
 
+    if let Some(trade_data) = result.as_mut() {
+        trade_data.lst_mint = MSOL_MINT.to_string();
+    }
+
     return result;
 }
 
@@ -473,3 +515,18 @@ fn get_sol_balance_change(
     let post_balance = post_balances[index];
     return post_balance as f64 - pre_balance as f64;
 }
+
+// The stake account is handed over as is, mSOL is minted for its delegated lamports.
+fn get_stake_account_lamports(
+    address: &String,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+) -> f64 {
+    accounts
+        .iter()
+        .position(|account| account == address)
+        .and_then(|index| pre_balances.get(index))
+        .map_or(0.0, |balance| {
+            balance.saturating_sub(STAKE_ACCOUNT_RENT_EXEMPT_RESERVE) as f64
+        })
+}
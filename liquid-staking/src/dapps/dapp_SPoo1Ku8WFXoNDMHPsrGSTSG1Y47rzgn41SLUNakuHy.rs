@@ -254,6 +254,11 @@ pub fn parse_trade_instruction(
         _ => {}
     }
 
+    // Every pool of the program and its forks mints its own LST.
+    if let Some(trade_data) = result.as_mut() {
+        trade_data.lst_mint = trade_data.pool_mint.to_string();
+    }
+
     return result;
 }
 
@@ -1,2 +1,3 @@
+pub mod dapp_5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx;
 pub mod dapp_MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD;
 pub mod dapp_SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy;
@@ -3,6 +3,7 @@
 #![allow(non_snake_case)]
 
 mod dapps;
+mod marinade_state;
 mod pb;
 mod utils;
 
@@ -13,6 +14,13 @@ use substreams_solana::pb::sf::solana::r#type::v1::{Block, InnerInstructions};
 use utils::convert_to_date;
 use utils::prepare_input_accounts;
 
+// The canonical SPL stake pool program and Sanctum's deployments of it.
+const SPL_STAKE_POOL_PROGRAMS: [&str; 3] = [
+    "SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy",
+    "SP12tWFxD9oJsVWNavTTBZvMbA6gkAmxtVgxdqvyvhY",
+    "SPMBzsVUuoHA4Jm6KunbsotaahvVikZs1JyTW6iJvbn",
+];
+
 #[substreams::handlers::map]
 fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
     let slot = block.slot;
@@ -25,6 +33,10 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
         let accounts = trx.resolved_accounts_as_strings();
         if let Some(transaction) = trx.transaction {
             let meta = trx.meta.unwrap();
+            // A failed transaction stakes nothing, its instructions only look like it did.
+            if meta.err.is_some() {
+                continue;
+            }
             let pre_balances = meta.pre_balances;
             let post_balances = meta.post_balances;
             let pre_token_balances = meta.pre_token_balances;
@@ -108,6 +120,40 @@ fn get_trade_data(
                     false,
                 );
         }
+        "SP12tWFxD9oJsVWNavTTBZvMbA6gkAmxtVgxdqvyvhY"
+        | "SPMBzsVUuoHA4Jm6KunbsotaahvVikZs1JyTW6iJvbn" => {
+            result =
+                dapps::dapp_SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                    accounts,
+                    log_messages,
+                    pre_balances,
+                    post_balances,
+                    pre_token_balances,
+                    post_token_balances,
+                    inner_instructions,
+                    dapp_address,
+                    &"".to_string(),
+                    idx,
+                    0,
+                    false,
+                );
+        }
+        "5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx" => {
+            result =
+                dapps::dapp_5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                    accounts,
+                    inner_instructions,
+                    dapp_address,
+                    &"".to_string(),
+                    idx,
+                    0,
+                    false,
+                );
+        }
         "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD" => {
             result =
                 dapps::dapp_MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD::parse_trade_instruction(
@@ -153,7 +199,7 @@ fn get_trade_data(
                             true
                         );
                     }
-                    else if inner_program.eq("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy") {
+                    else if SPL_STAKE_POOL_PROGRAMS.contains(&inner_program.as_str()) {
                         let inner_input_accounts =
                             prepare_input_accounts(&inner_inst.accounts, accounts);
                         result = dapps::dapp_SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy::parse_trade_instruction(
@@ -167,7 +213,22 @@ fn get_trade_data(
                             post_token_balances,
                             inner_instructions,
                             dapp_address,
-                            &"SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy".to_string(),
+                            inner_program,
+                            idx,
+                            inner_idx,
+                            true
+                        );
+                    }
+                    else if inner_program.eq("5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx") {
+                        let inner_input_accounts =
+                            prepare_input_accounts(&inner_inst.accounts, accounts);
+                        result = dapps::dapp_5ocnV1qiCgaQR8Jb8xWnVbApfaygJ8tNoZfgPwsgx9kx::parse_trade_instruction(
+                            inner_inst.data.clone(),
+                            inner_input_accounts,
+                            accounts,
+                            inner_instructions,
+                            dapp_address,
+                            inner_program,
                             idx,
                             inner_idx,
                             true
@@ -0,0 +1,160 @@
+use epoch_schedule::SLOTS_PER_EPOCH;
+use substreams::scalar::BigInt;
+use substreams::store::{
+    StoreAdd, StoreAddBigInt, StoreGet, StoreGetBigInt, StoreGetFloat64, StoreNew, StoreSet,
+    StoreSetFloat64,
+};
+
+use crate::pb::sf::solana::liquid::staking::v1::{
+    MarinadeExchangeRate, MarinadeExchangeRates, Output, TradeData,
+};
+
+const MARINADE_PROGRAM_ADDRESS: &str = "MarBmsSgKXdrN1egZf5sqe1TMai9K1rChYNDJgjq7aD";
+
+// Both deposit kinds mint mSOL at the current price for the lamports they add to the
+// stake, so staked lamports over mSOL minted is the exchange rate as of the last deposit.
+// Deposits are only matched against the SOL that reaches the reserve, the part swapped
+// against the liquidity pool's mSOL leg is neither staked nor minted.
+#[substreams::handlers::store]
+fn store_marinade_price(output: Output, store: StoreSetFloat64) {
+    for (ordinal, td) in get_marinade_events(&output) {
+        let is_deposit =
+            td.instruction_type == "Deposit" || td.instruction_type == "DepositStakeAccount";
+        if is_deposit && td.staked_amount > 0.0 && td.mint_amount > 0.0 {
+            store.set(
+                ordinal,
+                &td.stake_pool,
+                &(td.staked_amount / td.mint_amount),
+            );
+        }
+    }
+}
+
+// Total staked follows Marinade's total virtual staked lamports: deposits and rewards add
+// to it, delayed unstake tickets leave it when ordered. Liquid unstakes only swap against
+// the liquidity pool and change neither side.
+//
+// The totals are running sums, they are only absolute because the module starts before
+// the program's deployment (see initialBlock in substreams.yaml). Tickets are valued at
+// the rate of the latest deposit before them, which lags the rate by at most the rewards
+// of cranks that ran since.
+#[substreams::handlers::store]
+fn store_marinade_state(output: Output, price_store: StoreGetFloat64, store: StoreAddBigInt) {
+    for (ordinal, td) in get_marinade_events(&output) {
+        let price = price_store.get_at(ordinal, &td.stake_pool).unwrap_or(0.0);
+        let (staked, supply) = match td.instruction_type.as_str() {
+            "Deposit" => (td.staked_amount, td.mint_amount),
+            // The stake account is only read from the balances, the minted mSOL shows the
+            // deposit went through.
+            "DepositStakeAccount" if td.mint_amount > 0.0 => (td.staked_amount, td.mint_amount),
+            "OrderUnstake" => (-td.burn_amount * price, -td.burn_amount),
+            // Cranks mint the protocol fee on top of the rewards.
+            "UpdateActive" | "UpdateDeactivated" => (td.staking_reward, td.mint_amount),
+            _ => continue,
+        };
+
+        store.add(
+            ordinal,
+            format!("total_staked:{}", td.stake_pool),
+            BigInt::from(staked.round() as i64),
+        );
+        store.add(
+            ordinal,
+            format!("msol_supply:{}", td.stake_pool),
+            BigInt::from(supply.round() as i64),
+        );
+        if td.staking_reward > 0.0 {
+            store.add(
+                ordinal,
+                format!("rewards:{}:{}", td.stake_pool, get_epoch(td)),
+                BigInt::from(td.staking_reward.round() as i64),
+            );
+        }
+    }
+}
+
+#[substreams::handlers::map]
+fn map_marinade_exchange_rate(
+    output: Output,
+    state_store: StoreGetBigInt,
+) -> Result<MarinadeExchangeRates, substreams::errors::Error> {
+    let mut data: Vec<MarinadeExchangeRate> = vec![];
+
+    for (_, td) in get_marinade_events(&output) {
+        if td.instruction_type != "UpdateActive" && td.instruction_type != "UpdateDeactivated" {
+            continue;
+        }
+
+        let total_staked = state_store
+            .get_last(format!("total_staked:{}", td.stake_pool))
+            .unwrap_or(BigInt::zero());
+        let msol_supply = state_store
+            .get_last(format!("msol_supply:{}", td.stake_pool))
+            .unwrap_or(BigInt::zero());
+
+        let supply = to_f64(&msol_supply);
+        let msol_price = if supply > 0.0 {
+            to_f64(&total_staked) / supply
+        } else {
+            0.0
+        };
+
+        let epoch = get_epoch(td);
+        let staking_reward = state_store
+            .get_last(format!("rewards:{}:{}", td.stake_pool, epoch))
+            .unwrap_or(BigInt::zero());
+
+        let row = MarinadeExchangeRate {
+            epoch,
+            block_date: td.block_date.to_string(),
+            block_time: td.block_time,
+            block_slot: td.block_slot,
+            tx_id: td.tx_id.to_string(),
+            state: td.stake_pool.to_string(),
+            msol_mint: td.pool_mint.to_string(),
+            total_staked_lamports: total_staked.to_string(),
+            msol_supply: msol_supply.to_string(),
+            msol_price,
+            tvl: to_f64(&total_staked) / 1e9,
+            staking_reward: to_f64(&staking_reward),
+        };
+
+        // One row per epoch, with the state after this block's cranks. Cranks of an epoch
+        // span several blocks, the last row of an epoch is its closing rate.
+        match data
+            .iter()
+            .position(|other| other.epoch == row.epoch && other.state == row.state)
+        {
+            Some(position) => data[position] = row,
+            None => data.push(row),
+        }
+    }
+
+    Ok(MarinadeExchangeRates { data })
+}
+
+fn get_marinade_events(output: &Output) -> Vec<(u64, &TradeData)> {
+    output
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, td)| get_dapp(td) == MARINADE_PROGRAM_ADDRESS)
+        .map(|(ordinal, td)| (ordinal as u64, td))
+        .collect()
+}
+
+fn get_epoch(td: &TradeData) -> u64 {
+    td.block_slot / SLOTS_PER_EPOCH
+}
+
+fn to_f64(value: &BigInt) -> f64 {
+    value.to_string().parse::<f64>().unwrap_or(0.0)
+}
+
+fn get_dapp(td: &TradeData) -> &String {
+    if td.is_inner_instruction {
+        &td.inner_program
+    } else {
+        &td.outer_program
+    }
+}
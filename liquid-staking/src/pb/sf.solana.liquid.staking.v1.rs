@@ -56,5 +56,47 @@ pub struct TradeData {
     pub staking_reward: f64,
     #[prost(string, required, tag="24")]
     pub liq_pool_sol_leg: ::prost::alloc::string::String,
+    #[prost(string, required, tag="25")]
+    pub lst_mint: ::prost::alloc::string::String,
+    #[prost(string, required, tag="26")]
+    pub dst_lst_mint: ::prost::alloc::string::String,
+    #[prost(double, required, tag="27")]
+    pub dst_amount: f64,
+    #[prost(double, required, tag="28")]
+    pub staked_amount: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MarinadeExchangeRates {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<MarinadeExchangeRate>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct MarinadeExchangeRate {
+    #[prost(uint64, required, tag="1")]
+    pub epoch: u64,
+    #[prost(string, required, tag="2")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="3")]
+    pub block_time: i64,
+    #[prost(uint64, required, tag="4")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="5")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag="6")]
+    pub state: ::prost::alloc::string::String,
+    #[prost(string, required, tag="7")]
+    pub msol_mint: ::prost::alloc::string::String,
+    #[prost(string, required, tag="8")]
+    pub total_staked_lamports: ::prost::alloc::string::String,
+    #[prost(string, required, tag="9")]
+    pub msol_supply: ::prost::alloc::string::String,
+    #[prost(double, required, tag="10")]
+    pub msol_price: f64,
+    #[prost(double, required, tag="11")]
+    pub tvl: f64,
+    #[prost(double, required, tag="12")]
+    pub staking_reward: f64,
 }
 // @@protoc_insertion_point(module)
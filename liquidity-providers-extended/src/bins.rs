@@ -0,0 +1,96 @@
+use substreams::store::{StoreAdd, StoreAddFloat64, StoreGet, StoreGetFloat64, StoreNew};
+
+use crate::pb::sf::solana::liquidity::providers::v1::{
+    BinLiquiditySnapshot, BinLiquiditySnapshots, Output, TradeData,
+};
+
+const DLMM_PROGRAM: &str = "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo";
+
+// Bin liquidity as deposited and withdrawn by LPs. Swaps are not applied: they move
+// liquidity between X and Y in the bins they cross, and the swap events only carry the
+// start and end bins, so the amounts here drift from the real depth of traded bins and
+// are best read as LP flows rather than reserves.
+
+#[substreams::handlers::store]
+fn store_bin_liquidity(output: Output, store: StoreAddFloat64) {
+    for (ordinal, td) in get_bin_events(&output) {
+        let sign = if td.instruction_type.starts_with("Remove") {
+            -1.0
+        } else {
+            1.0
+        };
+
+        for bin in td.bin_amounts.iter() {
+            store.add(
+                ordinal,
+                format!("x:{}:{}", td.pool, bin.bin_id),
+                sign * bin.amount_x,
+            );
+            store.add(
+                ordinal,
+                format!("y:{}:{}", td.pool, bin.bin_id),
+                sign * bin.amount_y,
+            );
+        }
+    }
+}
+
+#[substreams::handlers::map]
+fn map_bin_liquidity(
+    output: Output,
+    bins_store: StoreGetFloat64,
+) -> Result<BinLiquiditySnapshots, substreams::errors::Error> {
+    // One snapshot per bin touched in this block, read from the store at the end of the block.
+    let mut touched_bins: Vec<(&TradeData, i32)> = vec![];
+    for (_, td) in get_bin_events(&output) {
+        for bin in td.bin_amounts.iter() {
+            if !touched_bins
+                .iter()
+                .any(|(touched, bin_id)| touched.pool == td.pool && *bin_id == bin.bin_id)
+            {
+                touched_bins.push((td, bin.bin_id));
+            }
+        }
+    }
+
+    let mut data: Vec<BinLiquiditySnapshot> = vec![];
+    for (td, bin_id) in touched_bins {
+        let get_amount = |side: &str| {
+            bins_store
+                .get_last(format!("{}:{}:{}", side, td.pool, bin_id))
+                .unwrap_or(0.0)
+        };
+
+        data.push(BinLiquiditySnapshot {
+            block_date: td.block_date.to_string(),
+            block_time: td.block_time,
+            block_slot: td.block_slot,
+            lb_pair: td.pool.to_string(),
+            bin_id,
+            amount_x: get_amount("x"),
+            amount_y: get_amount("y"),
+        });
+    }
+
+    Ok(BinLiquiditySnapshots { data })
+}
+
+fn get_bin_events(output: &Output) -> Vec<(u64, &TradeData)> {
+    output
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, td)| {
+            !td.bin_amounts.is_empty() && !td.pool.is_empty() && get_dapp(td) == DLMM_PROGRAM
+        })
+        .map(|(ordinal, td)| (ordinal as u64, td))
+        .collect()
+}
+
+fn get_dapp(td: &TradeData) -> &String {
+    if td.is_inner_instruction {
+        &td.inner_program
+    } else {
+        &td.outer_program
+    }
+}
@@ -1,10 +1,14 @@
+use std::collections::HashMap;
 use std::io::Read;
 
+use base64::{engine::general_purpose, Engine as _};
+use borsh::{BorshDeserialize, BorshSerialize};
 use bytes::Buf;
+use log_tree_parser::LogContext;
 use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
 
 use crate::{
-    pb::sf::solana::liquidity::providers::v1::TradeData,
+    pb::sf::solana::liquidity::providers::v1::{DecodedLog, TradeData},
     utils::{get_mint_address_for, get_token_transfer},
 };
 
@@ -12,11 +16,21 @@ const IncreaseLiquidity: u64 = u64::from_le_bytes([46, 156, 243, 118, 13, 205, 2
 const IncreaseLiquidityV2: u64 = u64::from_le_bytes([133, 29, 89, 223, 69, 238, 176, 10]);
 const DecreaseLiquidity: u64 = u64::from_le_bytes([160, 38, 208, 111, 104, 91, 44, 1]);
 const DecreaseLiquidityV2: u64 = u64::from_le_bytes([58, 127, 188, 62, 79, 82, 196, 96]);
+const DecreaseLiquidityEventDiscriminator: [u8; 8] = [58, 222, 86, 58, 68, 50, 85, 56];
 const OpenPositionV2: u64 = u64::from_le_bytes([77, 184, 74, 214, 112, 86, 241, 199]);
 const OpenPosition: u64 = u64::from_le_bytes([135, 128, 47, 77, 15, 152, 240, 49]);
 const OpenPositionWithToken22Nft: u64 = u64::from_le_bytes([77, 255, 174, 82, 125, 29, 201, 46]);
 const CreatePool: u64 = u64::from_le_bytes([233, 146, 209, 142, 207, 104, 64, 188]);
 
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct OpenPositionLayout {
+    tickLowerIndex: i32,
+    tickUpperIndex: i32,
+    tickArrayLowerStartIndex: i32,
+    tickArrayUpperStartIndex: i32,
+    liquidity: u128,
+}
+
 pub fn parse_trade_instruction(
     signer: &String,
     bytes_stream: Vec<u8>,
@@ -26,9 +40,10 @@ pub fn parse_trade_instruction(
     post_token_balances: &Vec<TokenBalance>,
     inner_idx: u32,
     inner_instructions: &Vec<InnerInstructions>,
+    invocation: Option<&LogContext>,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(8);
-    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let mut td = TradeData::default();
@@ -37,9 +52,9 @@ pub fn parse_trade_instruction(
     match discriminator {
         IncreaseLiquidity => {
             td.instruction_type = "IncreaseLiquidity".to_string();
-            td.pool = input_accounts.get(2).unwrap().to_string();
-            td.account_a = input_accounts.get(9).unwrap().to_string();
-            td.account_b = input_accounts.get(10).unwrap().to_string();
+            td.pool = input_accounts.get(2)?.to_string();
+            td.account_a = input_accounts.get(9)?.to_string();
+            td.account_b = input_accounts.get(10)?.to_string();
             td.lp_wallet = signer.to_string();
 
             td.mint_a = get_mint_address_for(&td.account_a, post_token_balances, accounts);
@@ -59,18 +74,17 @@ pub fn parse_trade_instruction(
                 accounts,
                 "destination".to_string(),
             );
-            td.position = input_accounts.get(4).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(16);
-            let parsed_value_arr: [u8; 16] = parsed_value.to_vec().try_into().unwrap();
+            td.position = input_accounts.get(4)?.to_string();
+            let parsed_value_arr: [u8; 16] = rest.get(..16)?.try_into().ok()?;
             td.liquidity_index = u128::from_le_bytes(parsed_value_arr).to_string();
 
             result = Some(td);
         }
         IncreaseLiquidityV2 => {
             td.instruction_type = "IncreaseLiquidityV2".to_string();
-            td.pool = input_accounts.get(2).unwrap().to_string();
-            td.account_a = input_accounts.get(9).unwrap().to_string();
-            td.account_b = input_accounts.get(10).unwrap().to_string();
+            td.pool = input_accounts.get(2)?.to_string();
+            td.account_a = input_accounts.get(9)?.to_string();
+            td.account_b = input_accounts.get(10)?.to_string();
             td.lp_wallet = signer.to_string();
 
             td.mint_a = get_mint_address_for(&td.account_a, post_token_balances, accounts);
@@ -90,18 +104,17 @@ pub fn parse_trade_instruction(
                 accounts,
                 "destination".to_string(),
             );
-            td.position = input_accounts.get(4).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(16);
-            let parsed_value_arr: [u8; 16] = parsed_value.to_vec().try_into().unwrap();
+            td.position = input_accounts.get(4)?.to_string();
+            let parsed_value_arr: [u8; 16] = rest.get(..16)?.try_into().ok()?;
             td.liquidity_index = u128::from_le_bytes(parsed_value_arr).to_string();
 
             result = Some(td);
         }
         DecreaseLiquidity => {
             td.instruction_type = "DecreaseLiquidity".to_string();
-            td.pool = input_accounts.get(3).unwrap().to_string();
-            td.account_a = input_accounts.get(5).unwrap().to_string();
-            td.account_b = input_accounts.get(6).unwrap().to_string();
+            td.pool = input_accounts.get(3)?.to_string();
+            td.account_a = input_accounts.get(5)?.to_string();
+            td.account_b = input_accounts.get(6)?.to_string();
             td.lp_wallet = signer.to_string();
 
             td.mint_a = get_mint_address_for(&td.account_a, post_token_balances, accounts);
@@ -121,18 +134,18 @@ pub fn parse_trade_instruction(
                 accounts,
                 "source".to_string(),
             );
-            td.position = input_accounts.get(2).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(16);
-            let parsed_value_arr: [u8; 16] = parsed_value.to_vec().try_into().unwrap();
+            td.position = input_accounts.get(2)?.to_string();
+            let parsed_value_arr: [u8; 16] = rest.get(..16)?.try_into().ok()?;
             td.liquidity_index = u128::from_le_bytes(parsed_value_arr).to_string();
+            td.decoded_logs = invocation.and_then(get_decrease_liquidity_event);
 
             result = Some(td);
         }
         DecreaseLiquidityV2 => {
             td.instruction_type = "DecreaseLiquidityV2".to_string();
-            td.pool = input_accounts.get(3).unwrap().to_string();
-            td.account_a = input_accounts.get(5).unwrap().to_string();
-            td.account_b = input_accounts.get(6).unwrap().to_string();
+            td.pool = input_accounts.get(3)?.to_string();
+            td.account_a = input_accounts.get(5)?.to_string();
+            td.account_b = input_accounts.get(6)?.to_string();
             td.lp_wallet = signer.to_string();
 
             td.mint_a = get_mint_address_for(&td.account_a, post_token_balances, accounts);
@@ -152,18 +165,18 @@ pub fn parse_trade_instruction(
                 accounts,
                 "source".to_string(),
             );
-            td.position = input_accounts.get(2).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(16);
-            let parsed_value_arr: [u8; 16] = parsed_value.to_vec().try_into().unwrap();
+            td.position = input_accounts.get(2)?.to_string();
+            let parsed_value_arr: [u8; 16] = rest.get(..16)?.try_into().ok()?;
             td.liquidity_index = u128::from_le_bytes(parsed_value_arr).to_string();
+            td.decoded_logs = invocation.and_then(get_decrease_liquidity_event);
 
             result = Some(td);
         }
         OpenPositionV2 => {
             td.instruction_type = "OpenPositionV2".to_string();
-            td.pool = input_accounts.get(5).unwrap().to_string();
-            td.account_a = input_accounts.get(12).unwrap().to_string();
-            td.account_b = input_accounts.get(13).unwrap().to_string();
+            td.pool = input_accounts.get(5)?.to_string();
+            td.account_a = input_accounts.get(12)?.to_string();
+            td.account_b = input_accounts.get(13)?.to_string();
             td.lp_wallet = signer.to_string();
 
             td.mint_a = get_mint_address_for(&td.account_a, post_token_balances, accounts);
@@ -183,19 +196,21 @@ pub fn parse_trade_instruction(
                 accounts,
                 "destination".to_string(),
             );
-            td.position = input_accounts.get(9).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(32);
-            let parsed_value_arr_full: [u8; 32] = parsed_value.to_vec().try_into().unwrap();
-            let mut parsed_value_arr = &parsed_value_arr_full[16..];
+            td.position = input_accounts.get(9)?.to_string();
+            let mut parsed_value_arr = rest.get(16..32)?;
             td.liquidity_index = parsed_value_arr.get_u128_le().to_string();
 
+            let data = OpenPositionLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.tick_lower_index = data.tickLowerIndex;
+            td.tick_upper_index = data.tickUpperIndex;
+
             result = Some(td);
         }
         OpenPosition => {
             td.instruction_type = "OpenPosition".to_string();
-            td.pool = input_accounts.get(5).unwrap().to_string();
-            td.account_a = input_accounts.get(12).unwrap().to_string();
-            td.account_b = input_accounts.get(13).unwrap().to_string();
+            td.pool = input_accounts.get(5)?.to_string();
+            td.account_a = input_accounts.get(12)?.to_string();
+            td.account_b = input_accounts.get(13)?.to_string();
             td.lp_wallet = signer.to_string();
 
             td.mint_a = get_mint_address_for(&td.account_a, post_token_balances, accounts);
@@ -215,19 +230,21 @@ pub fn parse_trade_instruction(
                 accounts,
                 "destination".to_string(),
             );
-            td.position = input_accounts.get(9).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(32);
-            let parsed_value_arr_full: [u8; 32] = parsed_value.to_vec().try_into().unwrap();
-            let mut parsed_value_arr = &parsed_value_arr_full[16..];
+            td.position = input_accounts.get(9)?.to_string();
+            let mut parsed_value_arr = rest.get(16..32)?;
             td.liquidity_index = parsed_value_arr.get_u128_le().to_string();
 
+            let data = OpenPositionLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.tick_lower_index = data.tickLowerIndex;
+            td.tick_upper_index = data.tickUpperIndex;
+
             result = Some(td);
         }
         OpenPositionWithToken22Nft => {
             td.instruction_type = "OpenPositionWithToken22Nft".to_string();
-            td.pool = input_accounts.get(4).unwrap().to_string();
-            td.account_a = input_accounts.get(11).unwrap().to_string();
-            td.account_b = input_accounts.get(12).unwrap().to_string();
+            td.pool = input_accounts.get(4)?.to_string();
+            td.account_a = input_accounts.get(11)?.to_string();
+            td.account_b = input_accounts.get(12)?.to_string();
             td.lp_wallet = signer.to_string();
 
             td.mint_a = get_mint_address_for(&td.account_a, post_token_balances, accounts);
@@ -248,13 +265,19 @@ pub fn parse_trade_instruction(
                 "destination".to_string(),
             );
 
+            td.position = input_accounts.get(8)?.to_string();
+            let data = OpenPositionLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            td.liquidity_index = data.liquidity.to_string();
+            td.tick_lower_index = data.tickLowerIndex;
+            td.tick_upper_index = data.tickUpperIndex;
+
             result = Some(td);
         }
         CreatePool => {
             td.instruction_type = "CreatePool".to_string();
-            td.pool = input_accounts.get(1).unwrap().to_string();
-            td.account_a = input_accounts.get(5).unwrap().to_string();
-            td.account_b = input_accounts.get(6).unwrap().to_string();
+            td.pool = input_accounts.get(1)?.to_string();
+            td.account_a = input_accounts.get(5)?.to_string();
+            td.account_b = input_accounts.get(6)?.to_string();
             td.lp_wallet = signer.to_string();
 
             td.mint_a = get_mint_address_for(&td.account_a, post_token_balances, accounts);
@@ -282,3 +305,60 @@ pub fn parse_trade_instruction(
 
     return result;
 }
+
+#[derive(BorshDeserialize, Debug)]
+struct DecreaseLiquidityEvent {
+    position_nft_mint: [u8; 32],
+    liquidity: u128,
+    decrease_amount_0: u64,
+    decrease_amount_1: u64,
+    fee_amount_0: u64,
+    fee_amount_1: u64,
+    reward_amounts: [u64; 3],
+    transfer_fee_0: u64,
+    transfer_fee_1: u64,
+}
+
+// The transfers out of the vaults mix the withdrawn liquidity with the fees collected on
+// the way, only the event logged by the program tells them apart.
+fn get_decrease_liquidity_event(invocation: &LogContext) -> Option<DecodedLog> {
+    invocation.program_data.iter().find_map(|program_data| {
+        let bytes_stream = general_purpose::STANDARD.decode(program_data).ok()?;
+        if bytes_stream.get(..8)? != DecreaseLiquidityEventDiscriminator {
+            return None;
+        }
+        let event = DecreaseLiquidityEvent::deserialize(&mut &bytes_stream[8..]).ok()?;
+
+        let mut fields = HashMap::new();
+        fields.insert("liquidity".to_string(), event.liquidity.to_string());
+        fields.insert(
+            "decrease_amount_0".to_string(),
+            event.decrease_amount_0.to_string(),
+        );
+        fields.insert(
+            "decrease_amount_1".to_string(),
+            event.decrease_amount_1.to_string(),
+        );
+        fields.insert("fee_amount_0".to_string(), event.fee_amount_0.to_string());
+        fields.insert("fee_amount_1".to_string(), event.fee_amount_1.to_string());
+        for (index, reward_amount) in event.reward_amounts.iter().enumerate() {
+            fields.insert(
+                format!("reward_amount_{}", index),
+                reward_amount.to_string(),
+            );
+        }
+        fields.insert(
+            "transfer_fee_0".to_string(),
+            event.transfer_fee_0.to_string(),
+        );
+        fields.insert(
+            "transfer_fee_1".to_string(),
+            event.transfer_fee_1.to_string(),
+        );
+
+        Some(DecodedLog {
+            event_name: "DecreaseLiquidityEvent".to_string(),
+            fields,
+        })
+    })
+}
@@ -4,7 +4,7 @@ use core::str;
 use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
 
 use crate::{
-    pb::sf::solana::liquidity::providers::v1::{TradeData, DecodedLog, InstructionData},
+    pb::sf::solana::liquidity::providers::v1::{BinAmount, TradeData, DecodedLog, InstructionData},
     utils::{get_mint_address_for, get_token_transfer},
 };
 
@@ -38,6 +38,7 @@ const RebalanceLiquidity: u64 = u64::from_le_bytes([92, 4, 176, 193, 119, 185, 8
 // Event detection constants
 const EVENT_LOG_DISCRIMINATOR: [u8; 8] = [228, 69, 165, 46, 81, 203, 154, 29];
 const REBALANCING_EVENT_DISCRIMINATOR: [u8; 8] = [0, 109, 117, 179, 61, 91, 199, 200];
+const REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR: [u8; 8] = [116, 244, 97, 232, 103, 31, 152, 58];
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
 #[repr(u8)]
@@ -109,6 +110,32 @@ struct AddLiquidityByStrategyLayout {
     strategyParameters: StrategyParametersLayout,
 }
 
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct BinLiquidityDistributionByWeightLayout {
+    binId: i32,
+    weight: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct AddLiquidityByWeightLayout {
+    amountX: u64,
+    amountY: u64,
+    activeId: i32,
+    maxActiveBinSlippage: i32,
+    binLiquidityDist: Vec<BinLiquidityDistributionByWeightLayout>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct BinLiquidityReductionLayout {
+    binId: i32,
+    bpsToRemove: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct RemoveLiquidityLayout {
+    binLiquidityRemoval: Vec<BinLiquidityReductionLayout>,
+}
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
 struct AddLiquidityByStrategyOneSideLayout {
     amount: u64,
@@ -198,6 +225,15 @@ struct RebalancingEvent {
     rewards: [u64; 2],
 }
 
+#[derive(BorshDeserialize, Debug)]
+struct RemoveLiquidityEvent {
+    lb_pair: [u8; 32],
+    from: [u8; 32],
+    position: [u8; 32],
+    amounts: [u64; 2],
+    active_bin_id: i32,
+}
+
 // Helper functions for event detection and parsing
 
 fn is_anchor_event(data: &[u8]) -> bool {
@@ -357,6 +393,21 @@ pub fn parse_trade_instruction(
             );
             td.position = input_accounts.get(0).unwrap().to_string();
 
+            let data: AddLiquidityByWeightLayout =
+                AddLiquidityByWeightLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            let weights: Vec<(i32, u64)> = data
+                .binLiquidityDist
+                .iter()
+                .map(|dist| (dist.binId, dist.weight as u64))
+                .collect();
+            td.active_bin_id = Some(data.activeId);
+            td.bin_amounts = get_bin_amounts(
+                &weights,
+                data.activeId,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
+
             result = Some(td);
         }
         RemoveLiquidity => {
@@ -385,6 +436,22 @@ pub fn parse_trade_instruction(
             );
             td.position = input_accounts.get(0).unwrap().to_string();
 
+            let data: RemoveLiquidityLayout =
+                RemoveLiquidityLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            let weights: Vec<(i32, u64)> = data
+                .binLiquidityRemoval
+                .iter()
+                .map(|removal| (removal.binId, removal.bpsToRemove as u64))
+                .collect();
+            (td.active_bin_id, td.bin_amounts) = get_removed_bin_amounts(
+                &weights,
+                &td.pool,
+                inner_idx,
+                inner_instructions,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
+
             result = Some(td);
         }
         AddLiquidityOneSide => {
@@ -474,6 +541,13 @@ pub fn parse_trade_instruction(
             td.strategy_type = data.strategyParameters.strategyType.to_proto_struct();
             td.tick_lower_index = data.strategyParameters.minBinId;
             td.tick_upper_index = data.strategyParameters.maxBinId;
+            td.active_bin_id = Some(data.activeId);
+            td.bin_amounts = get_bin_amounts(
+                &get_strategy_weights(&data.strategyParameters, data.activeId),
+                data.activeId,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
 
             result = Some(td);
         }
@@ -508,6 +582,13 @@ pub fn parse_trade_instruction(
             td.strategy_type = data.strategyParameters.strategyType.to_proto_struct();
             td.tick_lower_index = data.strategyParameters.minBinId;
             td.tick_upper_index = data.strategyParameters.maxBinId;
+            td.active_bin_id = Some(data.activeId);
+            td.bin_amounts = get_bin_amounts(
+                &get_strategy_weights(&data.strategyParameters, data.activeId),
+                data.activeId,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
 
             result = Some(td);
         }
@@ -569,6 +650,17 @@ pub fn parse_trade_instruction(
             td.tick_lower_index = data.fromBinId;
             td.tick_upper_index = data.toBinId;
             td.bps_to_remove = Some(data.bpsToRemove as u32);
+            let weights: Vec<(i32, u64)> = (data.fromBinId..=data.toBinId)
+                .map(|bin_id| (bin_id, data.bpsToRemove as u64))
+                .collect();
+            (td.active_bin_id, td.bin_amounts) = get_removed_bin_amounts(
+                &weights,
+                &td.pool,
+                inner_idx,
+                inner_instructions,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
 
             result = Some(td);
         }
@@ -768,6 +860,13 @@ pub fn parse_trade_instruction(
             td.strategy_type = data.strategyParameters.strategyType.to_proto_struct();
             td.tick_lower_index = data.strategyParameters.minBinId;
             td.tick_upper_index = data.strategyParameters.maxBinId;
+            td.active_bin_id = Some(data.activeId);
+            td.bin_amounts = get_bin_amounts(
+                &get_strategy_weights(&data.strategyParameters, data.activeId),
+                data.activeId,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
 
             result = Some(td);
         }
@@ -883,6 +982,13 @@ pub fn parse_trade_instruction(
             td.strategy_type = data.strategyParameters.strategyType.to_proto_struct();
             td.tick_lower_index = data.strategyParameters.minBinId;
             td.tick_upper_index = data.strategyParameters.maxBinId;
+            td.active_bin_id = Some(data.activeId);
+            td.bin_amounts = get_bin_amounts(
+                &get_strategy_weights(&data.strategyParameters, data.activeId),
+                data.activeId,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
 
             result = Some(td);
         }
@@ -911,6 +1017,22 @@ pub fn parse_trade_instruction(
             );
             td.position = input_accounts.get(0).unwrap().to_string();
 
+            let data: RemoveLiquidityLayout =
+                RemoveLiquidityLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+            let weights: Vec<(i32, u64)> = data
+                .binLiquidityRemoval
+                .iter()
+                .map(|removal| (removal.binId, removal.bpsToRemove as u64))
+                .collect();
+            (td.active_bin_id, td.bin_amounts) = get_removed_bin_amounts(
+                &weights,
+                &td.pool,
+                inner_idx,
+                inner_instructions,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
+
             result = Some(td);
         }
         RemoveLiquidityByRange2 => {
@@ -944,6 +1066,17 @@ pub fn parse_trade_instruction(
             td.tick_lower_index = data.fromBinId;
             td.tick_upper_index = data.toBinId;
             td.bps_to_remove = Some(data.bpsToRemove as u32);
+            let weights: Vec<(i32, u64)> = (data.fromBinId..=data.toBinId)
+                .map(|bin_id| (bin_id, data.bpsToRemove as u64))
+                .collect();
+            (td.active_bin_id, td.bin_amounts) = get_removed_bin_amounts(
+                &weights,
+                &td.pool,
+                inner_idx,
+                inner_instructions,
+                td.token_a_amount,
+                td.token_b_amount,
+            );
 
             result = Some(td);
         },
@@ -1028,3 +1161,105 @@ pub fn parse_trade_instruction(
 
     return result;
 }
+
+// Mirrors the SDK's weight shapes: flat for Spot, peaking at the active bin for Curve and
+// growing away from it for BidAsk. The balanced/imbalanced variants share the same shape.
+fn get_strategy_weights(params: &StrategyParametersLayout, active_id: i32) -> Vec<(i32, u64)> {
+    let max_distance = i32::max(
+        (params.minBinId - active_id).abs(),
+        (params.maxBinId - active_id).abs(),
+    ) as u64;
+
+    (params.minBinId..=params.maxBinId)
+        .map(|bin_id| {
+            let distance = (bin_id - active_id).abs() as u64;
+            let weight = match params.strategyType {
+                StrategyTypeLayout::Curve
+                | StrategyTypeLayout::CurveBalanced
+                | StrategyTypeLayout::CurveImBalanced => max_distance + 1 - distance,
+                StrategyTypeLayout::BidAsk
+                | StrategyTypeLayout::BidAskBalanced
+                | StrategyTypeLayout::BidAskImBalanced => distance + 1,
+                _ => 1,
+            };
+            (bin_id, weight)
+        })
+        .collect()
+}
+
+// Bins above the active bin only hold X and bins below only hold Y, the active bin takes
+// its weighted share of both.
+fn get_bin_amounts(
+    weights: &Vec<(i32, u64)>,
+    active_id: i32,
+    amount_x: f64,
+    amount_y: f64,
+) -> Vec<BinAmount> {
+    let total_x_weight: u64 = weights
+        .iter()
+        .filter(|(bin_id, _)| *bin_id >= active_id)
+        .map(|(_, weight)| weight)
+        .sum();
+    let total_y_weight: u64 = weights
+        .iter()
+        .filter(|(bin_id, _)| *bin_id <= active_id)
+        .map(|(_, weight)| weight)
+        .sum();
+
+    weights
+        .iter()
+        .filter(|(_, weight)| *weight > 0)
+        .map(|(bin_id, weight)| BinAmount {
+            bin_id: *bin_id,
+            amount_x: if *bin_id >= active_id && total_x_weight > 0 {
+                amount_x * *weight as f64 / total_x_weight as f64
+            } else {
+                0.0
+            },
+            amount_y: if *bin_id <= active_id && total_y_weight > 0 {
+                amount_y * *weight as f64 / total_y_weight as f64
+            } else {
+                0.0
+            },
+        })
+        .collect()
+}
+
+// Removals don't carry the active bin, it is read from the RemoveLiquidity event the
+// program emits by self CPI. The withdrawn amounts then follow the same split as adds.
+fn get_removed_bin_amounts(
+    weights: &Vec<(i32, u64)>,
+    pool: &String,
+    input_inner_idx: u32,
+    inner_instructions: &Vec<InnerInstructions>,
+    amount_x: f64,
+    amount_y: f64,
+) -> (Option<i32>, Vec<BinAmount>) {
+    match get_removal_active_bin_id(pool, input_inner_idx, inner_instructions) {
+        Some(active_id) => (
+            Some(active_id),
+            get_bin_amounts(weights, active_id, amount_x, amount_y),
+        ),
+        None => (None, vec![]),
+    }
+}
+
+fn get_removal_active_bin_id(
+    pool: &String,
+    input_inner_idx: u32,
+    inner_instructions: &Vec<InnerInstructions>,
+) -> Option<i32> {
+    inner_instructions
+        .iter()
+        .flat_map(|inner_instruction| inner_instruction.instructions.iter().enumerate())
+        .filter(|(inner_idx, _)| input_inner_idx == 0 || *inner_idx as u32 > input_inner_idx)
+        .filter(|(_, inner_inst)| is_anchor_event(&inner_inst.data))
+        .filter(|(_, inner_inst)| {
+            get_event_discriminator(&inner_inst.data) == Some(REMOVE_LIQUIDITY_EVENT_DISCRIMINATOR)
+        })
+        .filter_map(|(_, inner_inst)| {
+            RemoveLiquidityEvent::deserialize(&mut inner_inst.data.get(16..)?).ok()
+        })
+        .find(|event| bs58::encode(&event.lb_pair).into_string() == *pool)
+        .map(|event| event.active_bin_id)
+}
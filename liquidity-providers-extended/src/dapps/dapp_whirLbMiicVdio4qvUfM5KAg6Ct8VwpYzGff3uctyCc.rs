@@ -2,7 +2,7 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBalance};
 
 use crate::{
-    pb::sf::solana::liquidity::providers::v1::TradeData,
+    pb::sf::solana::liquidity::providers::v1::{InstructionData, TradeData},
     utils::{get_mint_address_for, get_token_transfer},
 };
 
@@ -76,8 +76,8 @@ pub fn parse_trade_instruction(
     inner_idx: u32,
     inner_instructions: &Vec<InnerInstructions>,
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(8);
-    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    let disc_bytes_arr: [u8; 8] = bytes_stream.get(..8)?.try_into().ok()?;
+    let rest = &bytes_stream[8..];
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let mut td = TradeData::default();
@@ -86,9 +86,9 @@ pub fn parse_trade_instruction(
     match discriminator {
         IncreaseLiquidity => {
             td.instruction_type = "IncreaseLiquidity".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.account_a = input_accounts.get(7).unwrap().to_string();
-            td.account_b = input_accounts.get(8).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.account_a = input_accounts.get(7)?.to_string();
+            td.account_b = input_accounts.get(8)?.to_string();
 
             td.lp_wallet = signer.to_string();
 
@@ -109,18 +109,17 @@ pub fn parse_trade_instruction(
                 accounts,
                 "destination".to_string(),
             );
-            td.position = input_accounts.get(3).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(16);
-            let parsed_value_arr: [u8; 16] = parsed_value.to_vec().try_into().unwrap();
+            td.position = input_accounts.get(3)?.to_string();
+            let parsed_value_arr: [u8; 16] = rest.get(..16)?.try_into().ok()?;
             td.liquidity_index = u128::from_le_bytes(parsed_value_arr).to_string();
 
             result = Some(td);
         }
         DecreaseLiquidity => {
             td.instruction_type = "DecreaseLiquidity".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.account_a = input_accounts.get(7).unwrap().to_string();
-            td.account_b = input_accounts.get(8).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.account_a = input_accounts.get(7)?.to_string();
+            td.account_b = input_accounts.get(8)?.to_string();
 
             td.lp_wallet = signer.to_string();
 
@@ -141,18 +140,17 @@ pub fn parse_trade_instruction(
                 accounts,
                 "source".to_string(),
             );
-            td.position = input_accounts.get(3).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(16);
-            let parsed_value_arr: [u8; 16] = parsed_value.to_vec().try_into().unwrap();
+            td.position = input_accounts.get(3)?.to_string();
+            let parsed_value_arr: [u8; 16] = rest.get(..16)?.try_into().ok()?;
             td.liquidity_index = u128::from_le_bytes(parsed_value_arr).to_string();
 
             result = Some(td);
         }
         IncreaseLiquidityV2 => {
             td.instruction_type = "IncreaseLiquidityV2".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.account_a = input_accounts.get(11).unwrap().to_string();
-            td.account_b = input_accounts.get(12).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.account_a = input_accounts.get(11)?.to_string();
+            td.account_b = input_accounts.get(12)?.to_string();
 
             td.lp_wallet = signer.to_string();
 
@@ -173,18 +171,17 @@ pub fn parse_trade_instruction(
                 accounts,
                 "destination".to_string(),
             );
-            td.position = input_accounts.get(5).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(16);
-            let parsed_value_arr: [u8; 16] = parsed_value.to_vec().try_into().unwrap();
+            td.position = input_accounts.get(5)?.to_string();
+            let parsed_value_arr: [u8; 16] = rest.get(..16)?.try_into().ok()?;
             td.liquidity_index = u128::from_le_bytes(parsed_value_arr).to_string();
 
             result = Some(td);
         }
         DecreaseLiquidityV2 => {
             td.instruction_type = "DecreaseLiquidityV2".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.account_a = input_accounts.get(11).unwrap().to_string();
-            td.account_b = input_accounts.get(12).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.account_a = input_accounts.get(11)?.to_string();
+            td.account_b = input_accounts.get(12)?.to_string();
 
             td.lp_wallet = signer.to_string();
 
@@ -205,18 +202,17 @@ pub fn parse_trade_instruction(
                 accounts,
                 "source".to_string(),
             );
-            td.position = input_accounts.get(5).unwrap().to_string();
-            let (parsed_value, _) = rest.split_at(16);
-            let parsed_value_arr: [u8; 16] = parsed_value.to_vec().try_into().unwrap();
+            td.position = input_accounts.get(5)?.to_string();
+            let parsed_value_arr: [u8; 16] = rest.get(..16)?.try_into().ok()?;
             td.liquidity_index = u128::from_le_bytes(parsed_value_arr).to_string();
 
             result = Some(td);
         }
         CollectFees => {
             td.instruction_type = "CollectFees".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.account_a = input_accounts.get(5).unwrap().to_string();
-            td.account_b = input_accounts.get(7).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.account_a = input_accounts.get(5)?.to_string();
+            td.account_b = input_accounts.get(7)?.to_string();
 
             td.lp_wallet = signer.to_string();
 
@@ -237,15 +233,15 @@ pub fn parse_trade_instruction(
                 accounts,
                 "source".to_string(),
             );
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
 
             result = Some(td);
         }
         CollectFeesV2 => {
             td.instruction_type = "CollectFeesV2".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.account_a = input_accounts.get(7).unwrap().to_string();
-            td.account_b = input_accounts.get(9).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.account_a = input_accounts.get(7)?.to_string();
+            td.account_b = input_accounts.get(9)?.to_string();
 
             td.lp_wallet = signer.to_string();
 
@@ -266,14 +262,14 @@ pub fn parse_trade_instruction(
                 accounts,
                 "source".to_string(),
             );
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
 
             result = Some(td);
         }
         CollectReward => {
             td.instruction_type = "CollectReward".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.account_a = input_accounts.get(5).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.account_a = input_accounts.get(5)?.to_string();
             td.account_b = "".to_string();
 
             td.lp_wallet = signer.to_string();
@@ -295,14 +291,21 @@ pub fn parse_trade_instruction(
                 accounts,
                 "source".to_string(),
             );
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
+
+            let mut args = std::collections::HashMap::new();
+            args.insert("reward_index".to_string(), rest.get(0).unwrap_or(&0).to_string());
+            td.instruction_data = Some(InstructionData {
+                instruction_name: "CollectReward".to_string(),
+                args,
+            });
 
             result = Some(td);
         }
         CollectRewardV2 => {
             td.instruction_type = "CollectRewardV2".to_string();
-            td.pool = input_accounts.get(0).unwrap().to_string();
-            td.account_a = input_accounts.get(6).unwrap().to_string();
+            td.pool = input_accounts.get(0)?.to_string();
+            td.account_a = input_accounts.get(6)?.to_string();
             td.account_b = "".to_string();
 
             td.lp_wallet = signer.to_string();
@@ -324,17 +327,24 @@ pub fn parse_trade_instruction(
                 accounts,
                 "source".to_string(),
             );
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
+
+            let mut args = std::collections::HashMap::new();
+            args.insert("reward_index".to_string(), rest.get(0).unwrap_or(&0).to_string());
+            td.instruction_data = Some(InstructionData {
+                instruction_name: "CollectRewardV2".to_string(),
+                args,
+            });
 
             result = Some(td);
         }
         OpenPosition => {
             td.instruction_type = "OpenPosition".to_string();
-            td.pool = input_accounts.get(5).unwrap().to_string();
+            td.pool = input_accounts.get(5)?.to_string();
             td.lp_wallet = signer.to_string();
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
 
-            let data = OpenPositionLayout::deserialize(&mut rest.clone()).unwrap();
+            let data = OpenPositionLayout::deserialize(&mut rest.clone()).ok()?;
             td.tick_lower_index = data.tickLowerIndex;
             td.tick_upper_index = data.tickUpperIndex;
 
@@ -342,11 +352,11 @@ pub fn parse_trade_instruction(
         }
         OpenPositionWithMetadata => {
             td.instruction_type = "OpenPositionWithMetadata".to_string();
-            td.pool = input_accounts.get(6).unwrap().to_string();
+            td.pool = input_accounts.get(6)?.to_string();
             td.lp_wallet = signer.to_string();
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
 
-            let data = OpenPositionWithMetadataLayout::deserialize(&mut rest.clone()).unwrap();
+            let data = OpenPositionWithMetadataLayout::deserialize(&mut rest.clone()).ok()?;
             td.tick_lower_index = data.tickLowerIndex;
             td.tick_upper_index = data.tickUpperIndex;
 
@@ -354,12 +364,12 @@ pub fn parse_trade_instruction(
         }
         OpenBundledPosition => {
             td.instruction_type = "OpenBundledPosition".to_string();
-            td.pool = input_accounts.get(4).unwrap().to_string();
+            td.pool = input_accounts.get(4)?.to_string();
             td.lp_wallet = signer.to_string();
-            td.position = input_accounts.get(1).unwrap().to_string();
-            td.bundled_position = input_accounts.get(0).unwrap().to_string();
+            td.position = input_accounts.get(1)?.to_string();
+            td.bundled_position = input_accounts.get(0)?.to_string();
 
-            let data = OpenBundledPositionLayout::deserialize(&mut rest.clone()).unwrap();
+            let data = OpenBundledPositionLayout::deserialize(&mut rest.clone()).ok()?;
             td.tick_lower_index = data.tickLowerIndex;
             td.tick_upper_index = data.tickUpperIndex;
 
@@ -367,12 +377,12 @@ pub fn parse_trade_instruction(
         }
         OpenPositionWithTokenExtensions => {
             td.instruction_type = "OpenPositionWithTokenExtensions".to_string();
-            td.pool = input_accounts.get(5).unwrap().to_string();
+            td.pool = input_accounts.get(5)?.to_string();
             td.lp_wallet = signer.to_string();
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
 
             let data =
-                OpenPositionWithTokenExtensionsLayout::deserialize(&mut rest.clone()).unwrap();
+                OpenPositionWithTokenExtensionsLayout::deserialize(&mut rest.clone()).ok()?;
             td.tick_lower_index = data.tickLowerIndex;
             td.tick_upper_index = data.tickUpperIndex;
 
@@ -381,22 +391,22 @@ pub fn parse_trade_instruction(
         ClosePosition => {
             td.instruction_type = "ClosePosition".to_string();
             td.lp_wallet = signer.to_string();
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
 
             result = Some(td);
         }
         CloseBundledPosition => {
             td.instruction_type = "CloseBundledPosition".to_string();
             td.lp_wallet = signer.to_string();
-            td.position = input_accounts.get(1).unwrap().to_string();
-            td.bundled_position = input_accounts.get(0).unwrap().to_string();
+            td.position = input_accounts.get(1)?.to_string();
+            td.bundled_position = input_accounts.get(0)?.to_string();
 
             result = Some(td);
         }
         ClosePositionWithTokenExtensions => {
             td.instruction_type = "ClosePositionWithTokenExtensions".to_string();
             td.lp_wallet = signer.to_string();
-            td.position = input_accounts.get(2).unwrap().to_string();
+            td.position = input_accounts.get(2)?.to_string();
 
             result = Some(td);
         }
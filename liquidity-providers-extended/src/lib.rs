@@ -2,15 +2,22 @@
 #![allow(unused_variables)]
 #![allow(non_snake_case)]
 
+mod bins;
 mod dapps;
 mod pb;
+mod positions;
 mod utils;
 
+use log_tree_parser::{parse_logs, LogContext, LogContextIterator};
 use pb::sf::solana::liquidity::providers::v1::{Output, TradeData};
 use substreams::log;
 use substreams_solana::pb::sf::solana::r#type::v1::{Block, InnerInstructions, TokenBalance};
 use utils::convert_to_date;
 
+// Programs whose parsers read the events they log, the log tree is only built for
+// transactions invoking them.
+const LOG_EVENT_PROGRAMS: [&str; 1] = ["CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK"];
+
 #[substreams::handlers::map]
 fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
     process_block(block)
@@ -27,6 +34,19 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
             let accounts = trx.resolved_accounts_as_strings();
             if let Some(transaction) = trx.transaction {
                 let meta = trx.meta.unwrap();
+                // Failed transactions move no liquidity, and their data may not even decode.
+                if meta.err.is_some() {
+                    continue;
+                }
+
+                let parsed_logs = if accounts
+                    .iter()
+                    .any(|account| LOG_EVENT_PROGRAMS.contains(&account.as_str()))
+                {
+                    parse_logs(&meta.log_messages)
+                } else {
+                    vec![]
+                };
 
                 let pre_token_balances = meta.pre_token_balances;
                 let post_token_balances = meta.post_token_balances;
@@ -54,6 +74,7 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
                         &"".to_string(),
                         0,
                         &inner_instructions.clone(),
+                        get_invocation(&parsed_logs, idx, None),
                     );
 
                     if trade_data.is_some() {
@@ -80,6 +101,7 @@ fn process_block(block: Block) -> Result<Output, substreams::errors::Error> {
                                     inner_program,
                                     inner_idx as u32,
                                     &inner_instructions.clone(),
+                                    get_invocation(&parsed_logs, idx, Some(inner_idx)),
                                 );
 
                                 if trade_data.is_some() {
@@ -113,25 +135,26 @@ fn get_trade_data(
     inner_program: &String,
     inner_instruction_index: u32,
     inner_insrtuctions: &Vec<InnerInstructions>,
+    invocation: Option<&LogContext>,
 ) -> Option<TradeData> {
     let input_accounts = prepare_input_accounts(account_indices, accounts);
     let signer = accounts.get(0).unwrap().to_string();
 
     let mut result = None;
     match dapp_address.as_str() {
-        // "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc" => {
-        //     result =
-        //         dapps::dapp_whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc::parse_trade_instruction(
-        //             &signer,
-        //             instruction_data,
-        //             &accounts,
-        //             input_accounts,
-        //             pre_token_balances,
-        //             post_token_balances,
-        //             inner_instruction_index,
-        //             inner_insrtuctions,
-        //         );
-        // }
+        "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc" => {
+            result =
+                dapps::dapp_whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc::parse_trade_instruction(
+                    &signer,
+                    instruction_data,
+                    &accounts,
+                    input_accounts,
+                    pre_token_balances,
+                    post_token_balances,
+                    inner_instruction_index,
+                    inner_insrtuctions,
+                );
+        }
         // "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP" => {
         //     result =
         //         dapps::dapp_9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP::parse_trade_instruction(
@@ -158,19 +181,20 @@ fn get_trade_data(
         //             inner_insrtuctions,
         //         );
         // }
-        // "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK" => {
-        //     result =
-        //         dapps::dapp_CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK::parse_trade_instruction(
-        //             &signer,
-        //             instruction_data,
-        //             &accounts,
-        //             input_accounts,
-        //             pre_token_balances,
-        //             post_token_balances,
-        //             inner_instruction_index,
-        //             inner_insrtuctions,
-        //         );
-        // }
+        "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK" => {
+            result =
+                dapps::dapp_CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK::parse_trade_instruction(
+                    &signer,
+                    instruction_data,
+                    &accounts,
+                    input_accounts,
+                    pre_token_balances,
+                    post_token_balances,
+                    inner_instruction_index,
+                    inner_insrtuctions,
+                    invocation,
+                );
+        }
         // "EewxydAPCCVuNEyrVN68PuSYdQ7wKn27V9Gjeoi8dy3S" => {
         //     result =
         //         dapps::dapp_EewxydAPCCVuNEyrVN68PuSYdQ7wKn27V9Gjeoi8dy3S::parse_trade_instruction(
@@ -308,6 +332,20 @@ fn get_trade_data(
     result
 }
 
+// Log roots follow the outer instructions, and the nodes under a root follow its inner
+// instructions.
+fn get_invocation(
+    parsed_logs: &Vec<LogContext>,
+    idx: usize,
+    inner_idx: Option<usize>,
+) -> Option<&LogContext> {
+    let root = parsed_logs.get(idx)?;
+    match inner_idx {
+        Some(inner_idx) => LogContextIterator::new(&root.children_nodes).nth(inner_idx),
+        None => Some(root),
+    }
+}
+
 fn prepare_input_accounts(account_indices: &Vec<u8>, accounts: &Vec<String>) -> Vec<String> {
     let mut instruction_accounts: Vec<String> = vec![];
     for (index, &el) in account_indices.iter().enumerate() {
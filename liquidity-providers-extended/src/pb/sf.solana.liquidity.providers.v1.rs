@@ -69,6 +69,20 @@ pub struct TradeData {
     pub decoded_logs: ::core::option::Option<DecodedLog>,
     #[prost(message, optional, tag="34")]
     pub instruction_data: ::core::option::Option<InstructionData>,
+    #[prost(message, repeated, tag="35")]
+    pub bin_amounts: ::prost::alloc::vec::Vec<BinAmount>,
+    #[prost(int32, optional, tag="36")]
+    pub active_bin_id: ::core::option::Option<i32>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BinAmount {
+    #[prost(int32, required, tag="1")]
+    pub bin_id: i32,
+    #[prost(double, required, tag="2")]
+    pub amount_x: f64,
+    #[prost(double, required, tag="3")]
+    pub amount_y: f64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -86,4 +100,96 @@ pub struct InstructionData {
     #[prost(map="string, string", tag="2")]
     pub args: ::std::collections::HashMap<::prost::alloc::string::String, ::prost::alloc::string::String>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PositionRange {
+    #[prost(string, required, tag="1")]
+    pub dapp: ::prost::alloc::string::String,
+    #[prost(string, required, tag="2")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, required, tag="3")]
+    pub position: ::prost::alloc::string::String,
+    #[prost(string, required, tag="4")]
+    pub bundled_position: ::prost::alloc::string::String,
+    #[prost(string, required, tag="5")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(int32, required, tag="6")]
+    pub tick_lower_index: i32,
+    #[prost(int32, required, tag="7")]
+    pub tick_upper_index: i32,
+    #[prost(int64, required, tag="8")]
+    pub opened_at: i64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PositionSnapshots {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<PositionSnapshot>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PositionSnapshot {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="2")]
+    pub block_time: i64,
+    #[prost(uint64, required, tag="3")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="4")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag="5")]
+    pub dapp: ::prost::alloc::string::String,
+    #[prost(string, required, tag="6")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, required, tag="7")]
+    pub position: ::prost::alloc::string::String,
+    #[prost(string, required, tag="8")]
+    pub bundled_position: ::prost::alloc::string::String,
+    #[prost(string, required, tag="9")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(int32, required, tag="10")]
+    pub tick_lower_index: i32,
+    #[prost(int32, required, tag="11")]
+    pub tick_upper_index: i32,
+    #[prost(string, required, tag="12")]
+    pub liquidity: ::prost::alloc::string::String,
+    #[prost(string, required, tag="13")]
+    pub fees_a: ::prost::alloc::string::String,
+    #[prost(string, required, tag="14")]
+    pub fees_b: ::prost::alloc::string::String,
+    #[prost(string, required, tag="15")]
+    pub reward_0: ::prost::alloc::string::String,
+    #[prost(string, required, tag="16")]
+    pub reward_1: ::prost::alloc::string::String,
+    #[prost(string, required, tag="17")]
+    pub reward_2: ::prost::alloc::string::String,
+    #[prost(string, required, tag="18")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="19")]
+    pub is_closed: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BinLiquiditySnapshots {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<BinLiquiditySnapshot>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BinLiquiditySnapshot {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="2")]
+    pub block_time: i64,
+    #[prost(uint64, required, tag="3")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="4")]
+    pub lb_pair: ::prost::alloc::string::String,
+    #[prost(int32, required, tag="5")]
+    pub bin_id: i32,
+    #[prost(double, required, tag="6")]
+    pub amount_x: f64,
+    #[prost(double, required, tag="7")]
+    pub amount_y: f64,
+}
 // @@protoc_insertion_point(module)
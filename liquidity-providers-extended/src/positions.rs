@@ -0,0 +1,200 @@
+use std::str::FromStr;
+
+use substreams::scalar::BigInt;
+use substreams::store::{
+    StoreAdd, StoreAddBigInt, StoreGet, StoreGetBigInt, StoreGetProto, StoreNew, StoreSet,
+    StoreSetProto,
+};
+
+use crate::pb::sf::solana::liquidity::providers::v1::{
+    Output, PositionRange, PositionSnapshot, PositionSnapshots, TradeData,
+};
+
+// Only concentrated liquidity programs keep one liquidity value and one tick range per position.
+const POSITION_DAPPS: [&str; 2] = [
+    "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+    "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK",
+];
+
+#[substreams::handlers::store]
+fn store_position_ranges(output: Output, store: StoreSetProto<PositionRange>) {
+    for (ordinal, td) in get_position_events(&output) {
+        if !td.instruction_type.starts_with("Open") {
+            continue;
+        }
+
+        store.set(
+            ordinal,
+            &td.position,
+            &PositionRange {
+                dapp: get_dapp(td).to_string(),
+                pool: td.pool.to_string(),
+                position: td.position.to_string(),
+                bundled_position: td.bundled_position.to_string(),
+                owner: td.lp_wallet.to_string(),
+                tick_lower_index: td.tick_lower_index,
+                tick_upper_index: td.tick_upper_index,
+                opened_at: td.block_time,
+            },
+        );
+    }
+}
+
+#[substreams::handlers::store]
+fn store_position_liquidity(output: Output, store: StoreAddBigInt) {
+    for (ordinal, td) in get_position_events(&output) {
+        if td.liquidity_index.is_empty() {
+            continue;
+        }
+
+        let liquidity = BigInt::from_str(&td.liquidity_index).unwrap_or(BigInt::zero());
+        if td.instruction_type.starts_with("DecreaseLiquidity") {
+            store.add(ordinal, &td.position, BigInt::zero() - liquidity);
+        } else if td.instruction_type.starts_with("IncreaseLiquidity")
+            || td.instruction_type.starts_with("Open")
+        {
+            store.add(ordinal, &td.position, liquidity);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_position_fees(output: Output, store: StoreAddBigInt) {
+    for (ordinal, td) in get_position_events(&output) {
+        if td.instruction_type.starts_with("CollectFees") {
+            store.add(
+                ordinal,
+                format!("fees_a:{}", td.position),
+                BigInt::from(td.raw_token_a_amount as u64),
+            );
+            store.add(
+                ordinal,
+                format!("fees_b:{}", td.position),
+                BigInt::from(td.raw_token_b_amount as u64),
+            );
+        } else if td.instruction_type.starts_with("DecreaseLiquidity") {
+            // CLMM pays out the accrued fees along with the withdrawn liquidity.
+            let fields = match td.decoded_logs.as_ref() {
+                Some(decoded_log) if decoded_log.event_name == "DecreaseLiquidityEvent" => {
+                    &decoded_log.fields
+                }
+                _ => continue,
+            };
+            let get_fee = |name: &str| {
+                fields
+                    .get(name)
+                    .and_then(|amount| amount.parse::<u64>().ok())
+                    .unwrap_or(0)
+            };
+            store.add(
+                ordinal,
+                format!("fees_a:{}", td.position),
+                BigInt::from(get_fee("fee_amount_0")),
+            );
+            store.add(
+                ordinal,
+                format!("fees_b:{}", td.position),
+                BigInt::from(get_fee("fee_amount_1")),
+            );
+        } else if td.instruction_type.starts_with("CollectReward") {
+            let reward_index = td
+                .instruction_data
+                .as_ref()
+                .and_then(|instruction_data| instruction_data.args.get("reward_index"))
+                .map(|reward_index| reward_index.to_string())
+                .unwrap_or("0".to_string());
+            store.add(
+                ordinal,
+                format!("reward_{}:{}", reward_index, td.position),
+                BigInt::from(td.raw_token_a_amount as u64),
+            );
+        }
+    }
+}
+
+#[substreams::handlers::map]
+fn map_position_snapshots(
+    output: Output,
+    ranges_store: StoreGetProto<PositionRange>,
+    liquidity_store: StoreGetBigInt,
+    fees_store: StoreGetBigInt,
+) -> Result<PositionSnapshots, substreams::errors::Error> {
+    // Keep the last event of every position touched in this block, the stores already
+    // hold the state at the end of the block.
+    let mut latest_events: Vec<&TradeData> = vec![];
+    for (_, td) in get_position_events(&output) {
+        match latest_events
+            .iter()
+            .position(|latest| latest.position == td.position)
+        {
+            Some(index) => latest_events[index] = td,
+            None => latest_events.push(td),
+        }
+    }
+
+    let mut data: Vec<PositionSnapshot> = vec![];
+    for td in latest_events {
+        let range = ranges_store.get_last(&td.position).unwrap_or_default();
+        let get_amount = |key: String| {
+            fees_store
+                .get_last(key)
+                .unwrap_or(BigInt::zero())
+                .to_string()
+        };
+
+        data.push(PositionSnapshot {
+            block_date: td.block_date.to_string(),
+            block_time: td.block_time,
+            block_slot: td.block_slot,
+            tx_id: td.tx_id.to_string(),
+            dapp: get_dapp(td).to_string(),
+            pool: if range.pool.is_empty() {
+                td.pool.to_string()
+            } else {
+                range.pool
+            },
+            position: td.position.to_string(),
+            bundled_position: td.bundled_position.to_string(),
+            owner: if range.owner.is_empty() {
+                td.lp_wallet.to_string()
+            } else {
+                range.owner
+            },
+            tick_lower_index: range.tick_lower_index,
+            tick_upper_index: range.tick_upper_index,
+            liquidity: liquidity_store
+                .get_last(&td.position)
+                .unwrap_or(BigInt::zero())
+                .to_string(),
+            fees_a: get_amount(format!("fees_a:{}", td.position)),
+            fees_b: get_amount(format!("fees_b:{}", td.position)),
+            reward_0: get_amount(format!("reward_0:{}", td.position)),
+            reward_1: get_amount(format!("reward_1:{}", td.position)),
+            reward_2: get_amount(format!("reward_2:{}", td.position)),
+            instruction_type: td.instruction_type.to_string(),
+            is_closed: td.instruction_type.starts_with("Close"),
+        });
+    }
+
+    Ok(PositionSnapshots { data })
+}
+
+fn get_position_events(output: &Output) -> Vec<(u64, &TradeData)> {
+    output
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, td)| {
+            !td.position.is_empty() && POSITION_DAPPS.contains(&get_dapp(td).as_str())
+        })
+        .map(|(ordinal, td)| (ordinal as u64, td))
+        .collect()
+}
+
+fn get_dapp(td: &TradeData) -> &String {
+    if td.is_inner_instruction {
+        &td.inner_program
+    } else {
+        &td.outer_program
+    }
+}
@@ -2,7 +2,7 @@ use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBala
 
 use crate::{
     pb::sf::solana::liquidity::providers::v1::TradeData,
-    utils::{get_mint_address_for, get_token_transfer},
+    utils::{get_lp_token_amount, get_mint_address_for, get_token_transfer},
 };
 
 const Deposit: u8 = 3;
@@ -53,6 +53,10 @@ pub fn parse_trade_instruction(
                 "destination".to_string(),
             );
 
+            td.lp_mint = input_accounts.get(5).unwrap_or(&"".to_string()).to_string();
+            td.lp_token_amount =
+                get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
             result = Some(td);
         }
         Withdraw => {
@@ -80,6 +84,10 @@ pub fn parse_trade_instruction(
                 "source".to_string(),
             );
 
+            td.lp_mint = input_accounts.get(5).unwrap_or(&"".to_string()).to_string();
+            td.lp_token_amount =
+                get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
             result = Some(td);
         }
         Initialize => {
@@ -107,6 +115,10 @@ pub fn parse_trade_instruction(
                 "destination".to_string(),
             );
 
+            td.lp_mint = input_accounts.get(7).unwrap_or(&"".to_string()).to_string();
+            td.lp_token_amount =
+                get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
             result = Some(td);
         }
         Initialize2 => {
@@ -139,6 +151,10 @@ pub fn parse_trade_instruction(
                     "destination".to_string(),
                 );
 
+                td.lp_mint = input_accounts.get(7).unwrap_or(&"".to_string()).to_string();
+                td.lp_token_amount =
+                    get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
                 result = Some(td);
             }
         }
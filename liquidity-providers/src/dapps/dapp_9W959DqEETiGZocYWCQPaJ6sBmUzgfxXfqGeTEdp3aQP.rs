@@ -2,7 +2,7 @@ use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBala
 
 use crate::{
     pb::sf::solana::liquidity::providers::v1::TradeData,
-    utils::{get_mint_address_for, get_token_transfer},
+    utils::{get_lp_token_amount, get_mint_address_for, get_token_transfer},
 };
 
 const DepositAllTokenTypes: u8 = 2;
@@ -51,6 +51,10 @@ pub fn parse_trade_instruction(
                 "destination".to_string(),
             );
 
+            td.lp_mint = input_accounts.get(7).unwrap_or(&"".to_string()).to_string();
+            td.lp_token_amount =
+                get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
             result = Some(td);
         }
         WithdrawAllTokenTypes => {
@@ -78,6 +82,10 @@ pub fn parse_trade_instruction(
                 "source".to_string(),
             );
 
+            td.lp_mint = input_accounts.get(3).unwrap_or(&"".to_string()).to_string();
+            td.lp_token_amount =
+                get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
             result = Some(td);
         }
         _ => {}
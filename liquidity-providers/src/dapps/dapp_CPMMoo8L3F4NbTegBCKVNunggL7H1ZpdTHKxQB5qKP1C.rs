@@ -2,7 +2,7 @@ use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstructions, TokenBala
 
 use crate::{
     pb::sf::solana::liquidity::providers::v1::TradeData,
-    utils::{get_mint_address_for, get_token_transfer},
+    utils::{get_lp_token_amount, get_mint_address_for, get_token_transfer},
 };
 
 const Deposit: u64 = u64::from_le_bytes([242, 35, 198, 137, 82, 225, 242, 182]);
@@ -53,6 +53,13 @@ pub fn parse_trade_instruction(
                 "destination".to_string(),
             );
 
+            td.lp_mint = input_accounts
+                .get(12)
+                .unwrap_or(&"".to_string())
+                .to_string();
+            td.lp_token_amount =
+                get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
             result = Some(td);
         }
         Withdraw => {
@@ -81,6 +88,13 @@ pub fn parse_trade_instruction(
                 "source".to_string(),
             );
 
+            td.lp_mint = input_accounts
+                .get(12)
+                .unwrap_or(&"".to_string())
+                .to_string();
+            td.lp_token_amount =
+                get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
             result = Some(td);
         }
         Initialize => {
@@ -109,6 +123,10 @@ pub fn parse_trade_instruction(
                 "destination".to_string(),
             );
 
+            td.lp_mint = input_accounts.get(6).unwrap_or(&"".to_string()).to_string();
+            td.lp_token_amount =
+                get_lp_token_amount(&td.lp_mint, inner_idx, inner_instructions, accounts);
+
             result = Some(td);
         }
         _ => {}
@@ -3,6 +3,7 @@
 #![allow(non_snake_case)]
 
 mod dapps;
+mod lp_shares;
 mod pb;
 mod utils;
 
@@ -132,7 +133,9 @@ fn get_trade_data(
                     inner_insrtuctions,
                 );
         }
-        "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP" => {
+        // Both are deployments of the SPL token swap program.
+        "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP"
+        | "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8" => {
             result =
                 dapps::dapp_9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP::parse_trade_instruction(
                     &signer,
@@ -300,6 +303,8 @@ fn get_trade_data(
                 10,
                 get_decimals(post_token_balances, &_result.account_b, accounts),
             )) as f64;
+        _result.reserve_a = get_token_balance(post_token_balances, &_result.account_a, accounts);
+        _result.reserve_b = get_token_balance(post_token_balances, &_result.account_b, accounts);
         result = Some(_result);
     }
 
@@ -319,18 +324,33 @@ fn get_decimals(
     address: &String,
     accounts: &Vec<String>,
 ) -> u32 {
-    let mut decimals: u32 = 0;
-    let index = accounts
+    let index = match accounts.iter().position(|r| r == address) {
+        Some(index) => index,
+        None => return 0,
+    };
+    post_token_balances
         .iter()
-        .position(|r| r == address)
-        .unwrap_or_default();
+        .filter(|token_balance| token_balance.account_index == index as u32)
+        .filter_map(|token_balance| token_balance.ui_token_amount.as_ref())
+        .last()
+        .map_or(0, |ui_token_amount| ui_token_amount.decimals)
+}
+
+fn get_token_balance(
+    post_token_balances: &Vec<TokenBalance>,
+    address: &String,
+    accounts: &Vec<String>,
+) -> f64 {
+    let index = match accounts.iter().position(|r| r == address) {
+        Some(index) => index,
+        None => return 0.0,
+    };
     post_token_balances
         .iter()
         .filter(|token_balance| token_balance.account_index == index as u32)
-        .for_each(|token_balance: &TokenBalance| {
-            decimals = token_balance.ui_token_amount.clone().unwrap().decimals;
-        });
-    decimals
+        .filter_map(|token_balance| token_balance.ui_token_amount.as_ref())
+        .last()
+        .map_or(0.0, |ui_token_amount| ui_token_amount.ui_amount)
 }
 
 fn filter_inner_instructions(
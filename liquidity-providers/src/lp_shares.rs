@@ -0,0 +1,212 @@
+use substreams::scalar::BigInt;
+use substreams::store::{
+    StoreAdd, StoreAddBigInt, StoreAddFloat64, StoreGet, StoreGetBigInt, StoreGetFloat64, StoreNew,
+};
+
+use crate::pb::sf::solana::liquidity::providers::v1::{
+    LpShareEvent, LpShareEvents, Output, TradeData,
+};
+
+// Constant-product programs that mint a fungible LP token for every deposit.
+const LP_SHARE_DAPPS: [&str; 4] = [
+    "675kPX9MHTjS2zt1qfr1NYHuzeLXfQM9H24wFSUt1Mp8",
+    "CPMMoo8L3F4NbTegBCKVNunggL7H1ZpdTHKxQB5qKP1C",
+    "9W959DqEETiGZocYWCQPaJ6sBmUzgfxXfqGeTEdp3aQP",
+    "SwaPpA9LAaLfeLi3a68M4DjnLqgtticKg6CnyNwgAC8",
+];
+
+#[substreams::handlers::store]
+fn store_lp_balances(output: Output, store: StoreAddBigInt) {
+    for (ordinal, td) in get_lp_events(&output) {
+        let amount = BigInt::from(td.lp_token_amount);
+        let amount = if is_withdrawal(td) {
+            BigInt::zero() - amount
+        } else {
+            amount
+        };
+
+        store.add(ordinal, format!("supply:{}", td.pool), &amount);
+        store.add(
+            ordinal,
+            format!("wallet:{}:{}", td.pool, td.lp_wallet),
+            &amount,
+        );
+    }
+}
+
+// Positions of a wallet are numbered, a deposit after a full exit opens a new one. Each
+// partial withdrawal keeps `remaining / (remaining + burned)` of the position, summed as
+// logarithms so the cost basis store can weight deposits by the share still held.
+#[substreams::handlers::store]
+fn store_lp_positions(output: Output, balances_store: StoreGetBigInt, store: StoreAddFloat64) {
+    for (ordinal, td) in get_lp_events(&output) {
+        let key = format!("{}:{}", td.pool, td.lp_wallet);
+        let remaining = balances_store
+            .get_at(ordinal, format!("wallet:{}", key))
+            .map_or(0.0, |balance| to_f64(&balance));
+        let amount = td.lp_token_amount as f64;
+
+        if !is_withdrawal(td) {
+            if remaining - amount <= 0.0 {
+                store.add(ordinal, format!("position:{}", key), 1.0);
+            }
+        } else if remaining > 0.0 {
+            store.add(
+                ordinal,
+                format!("log_kept:{}", key),
+                (remaining / (remaining + amount)).ln(),
+            );
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_lp_cost_basis(output: Output, positions_store: StoreGetFloat64, store: StoreAddFloat64) {
+    // Deposits are scaled up by the withdrawals made before them, so the basis left after
+    // any withdrawal is the scaled sum times the share kept since. The average entry per
+    // LP token, deposited / minted, doesn't depend on that share.
+    for (ordinal, td) in get_lp_events(&output) {
+        if is_withdrawal(td) {
+            continue;
+        }
+
+        let key = get_position_key(&positions_store, ordinal, td);
+        let scale = (-positions_store
+            .get_at(ordinal, format!("log_kept:{}:{}", td.pool, td.lp_wallet))
+            .unwrap_or(0.0))
+        .exp();
+        store.add(
+            ordinal,
+            format!("deposited_a:{}", key),
+            td.token_a_amount * scale,
+        );
+        store.add(
+            ordinal,
+            format!("deposited_b:{}", key),
+            td.token_b_amount * scale,
+        );
+        store.add(
+            ordinal,
+            format!("minted:{}", key),
+            td.lp_token_amount as f64 * scale,
+        );
+    }
+}
+
+#[substreams::handlers::map]
+fn map_lp_share_events(
+    output: Output,
+    balances_store: StoreGetBigInt,
+    positions_store: StoreGetFloat64,
+    cost_basis_store: StoreGetFloat64,
+) -> Result<LpShareEvents, substreams::errors::Error> {
+    let mut data: Vec<LpShareEvent> = vec![];
+    for (ordinal, td) in get_lp_events(&output) {
+        let key = format!("{}:{}", td.pool, td.lp_wallet);
+        let wallet_lp_balance = balances_store
+            .get_last(format!("wallet:{}", key))
+            .unwrap_or(BigInt::zero());
+        let lp_supply = balances_store
+            .get_last(format!("supply:{}", td.pool))
+            .unwrap_or(BigInt::zero());
+
+        let supply = to_f64(&lp_supply);
+        let share_of_pool = if supply > 0.0 {
+            to_f64(&wallet_lp_balance) / supply
+        } else {
+            0.0
+        };
+
+        // Everything is valued in token b at the pool price after the instruction.
+        let price = if td.reserve_a > 0.0 {
+            td.reserve_b / td.reserve_a
+        } else {
+            0.0
+        };
+        let value_of = |amount_a: f64, amount_b: f64| amount_a * price + amount_b;
+
+        let mut value_at_entry = value_of(td.token_a_amount, td.token_b_amount);
+        let mut value_at_exit = 0.0;
+        let mut impermanent_loss = 0.0;
+
+        if is_withdrawal(td) {
+            // Entry value is what the burnt LP tokens' share of the deposits would be worth
+            // if held, so exit / entry - 1 is the impermanent loss net of collected fees.
+            let position_key = get_position_key(&positions_store, ordinal, td);
+            let get_basis = |name: &str| {
+                cost_basis_store
+                    .get_at(ordinal, format!("{}:{}", name, position_key))
+                    .unwrap_or(0.0)
+            };
+            let minted = get_basis("minted");
+            value_at_entry = if minted > 0.0 {
+                td.lp_token_amount as f64 / minted
+                    * value_of(get_basis("deposited_a"), get_basis("deposited_b"))
+            } else {
+                0.0
+            };
+            value_at_exit = value_of(td.token_a_amount, td.token_b_amount);
+            if value_at_entry > 0.0 {
+                impermanent_loss = value_at_exit / value_at_entry - 1.0;
+            }
+        }
+
+        data.push(LpShareEvent {
+            block_date: td.block_date.to_string(),
+            block_time: td.block_time,
+            block_slot: td.block_slot,
+            tx_id: td.tx_id.to_string(),
+            dapp: get_dapp(td).to_string(),
+            pool: td.pool.to_string(),
+            lp_mint: td.lp_mint.to_string(),
+            lp_wallet: td.lp_wallet.to_string(),
+            instruction_type: td.instruction_type.to_string(),
+            token_a_amount: td.token_a_amount,
+            token_b_amount: td.token_b_amount,
+            lp_token_amount: td.lp_token_amount,
+            wallet_lp_balance: wallet_lp_balance.to_string(),
+            lp_supply: lp_supply.to_string(),
+            share_of_pool,
+            price,
+            value_at_entry,
+            value_at_exit,
+            impermanent_loss,
+        });
+    }
+
+    Ok(LpShareEvents { data })
+}
+
+fn get_lp_events(output: &Output) -> Vec<(u64, &TradeData)> {
+    output
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, td)| td.lp_token_amount > 0 && LP_SHARE_DAPPS.contains(&get_dapp(td).as_str()))
+        .map(|(ordinal, td)| (ordinal as u64, td))
+        .collect()
+}
+
+fn get_position_key(positions_store: &StoreGetFloat64, ordinal: u64, td: &TradeData) -> String {
+    let key = format!("{}:{}", td.pool, td.lp_wallet);
+    let position = positions_store
+        .get_at(ordinal, format!("position:{}", key))
+        .unwrap_or(0.0);
+    format!("{}:{}", key, position as u64)
+}
+
+fn is_withdrawal(td: &TradeData) -> bool {
+    td.instruction_type.starts_with("Withdraw")
+}
+
+fn to_f64(value: &BigInt) -> f64 {
+    value.to_string().parse::<f64>().unwrap_or(0.0)
+}
+
+fn get_dapp(td: &TradeData) -> &String {
+    if td.is_inner_instruction {
+        &td.inner_program
+    } else {
+        &td.outer_program
+    }
+}
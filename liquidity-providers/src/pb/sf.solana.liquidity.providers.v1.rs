@@ -46,5 +46,61 @@ pub struct TradeData {
     pub inner_program: ::prost::alloc::string::String,
     #[prost(uint32, required, tag="25")]
     pub inner_instruction_index: u32,
+    #[prost(string, required, tag="26")]
+    pub lp_mint: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="27")]
+    pub lp_token_amount: u64,
+    #[prost(double, required, tag="28")]
+    pub reserve_a: f64,
+    #[prost(double, required, tag="29")]
+    pub reserve_b: f64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LpShareEvents {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<LpShareEvent>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LpShareEvent {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="2")]
+    pub block_time: i64,
+    #[prost(uint64, required, tag="3")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="4")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag="5")]
+    pub dapp: ::prost::alloc::string::String,
+    #[prost(string, required, tag="6")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(string, required, tag="7")]
+    pub lp_mint: ::prost::alloc::string::String,
+    #[prost(string, required, tag="8")]
+    pub lp_wallet: ::prost::alloc::string::String,
+    #[prost(string, required, tag="9")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(double, required, tag="10")]
+    pub token_a_amount: f64,
+    #[prost(double, required, tag="11")]
+    pub token_b_amount: f64,
+    #[prost(uint64, required, tag="12")]
+    pub lp_token_amount: u64,
+    #[prost(string, required, tag="13")]
+    pub wallet_lp_balance: ::prost::alloc::string::String,
+    #[prost(string, required, tag="14")]
+    pub lp_supply: ::prost::alloc::string::String,
+    #[prost(double, required, tag="15")]
+    pub share_of_pool: f64,
+    #[prost(double, required, tag="16")]
+    pub price: f64,
+    #[prost(double, required, tag="17")]
+    pub value_at_entry: f64,
+    #[prost(double, required, tag="18")]
+    pub value_at_exit: f64,
+    #[prost(double, required, tag="19")]
+    pub impermanent_loss: f64,
 }
 // @@protoc_insertion_point(module)
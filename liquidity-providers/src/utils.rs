@@ -224,3 +224,67 @@ pub fn get_token_22_transfer(
 
     result
 }
+
+pub fn get_lp_token_amount(
+    lp_mint: &String,
+    input_inner_idx: u32,
+    inner_instructions: &Vec<InnerInstructions>,
+    accounts: &Vec<String>,
+) -> u64 {
+    let mut result = 0;
+    let mut result_assigned = false;
+
+    inner_instructions.iter().for_each(|inner_instruction| {
+        inner_instruction
+            .instructions
+            .iter()
+            .enumerate()
+            .for_each(|(inner_idx, inner_inst)| {
+                let inner_program = &accounts[inner_inst.program_id_index as usize];
+
+                if inner_program
+                    .as_str()
+                    .eq("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA")
+                    || inner_program
+                        .as_str()
+                        .eq("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb")
+                {
+                    if inner_inst.data.is_empty() {
+                        return;
+                    }
+                    let (discriminator_bytes, rest) = inner_inst.data.split_at(1);
+                    let discriminator: u8 = u8::from(discriminator_bytes[0]);
+
+                    // MintTo/MintToChecked take the mint first, Burn/BurnChecked take it second.
+                    let mint_position = match discriminator {
+                        7 | 14 => 0,
+                        8 | 15 => 1,
+                        _ => return,
+                    };
+
+                    let input_accounts = prepare_input_accounts(&inner_inst.accounts, accounts);
+                    let mint = input_accounts
+                        .get(mint_position)
+                        .unwrap_or(&"".to_string())
+                        .to_string();
+
+                    let condition = if input_inner_idx > 0 {
+                        inner_idx as u32 > input_inner_idx
+                    } else {
+                        true
+                    };
+
+                    if condition & mint.eq(lp_mint) {
+                        let data =
+                            TransferLayout::deserialize(&mut rest.clone()).unwrap_or_default();
+                        if !result_assigned {
+                            result = data.amount;
+                            result_assigned = true;
+                        }
+                    }
+                }
+            })
+    });
+
+    result
+}
@@ -0,0 +1,454 @@
+// Rebuilds the program invocation tree of a transaction from its log messages. Nodes
+// come out in invocation order, which is also the order of the transaction's outer and
+// inner instructions.
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LogContext {
+    pub program_id: String,
+    pub depth: usize,
+    pub id: usize,
+    pub compute_units: usize,
+    pub consumed_units: usize,
+    pub children: Vec<usize>, // Indices of child nodes in temp_nodes
+    pub children_nodes: Vec<LogContext>, // Actual child nodes
+    pub program_logs: Vec<String>,
+    pub program_data: Vec<String>,
+    pub failure_message: Option<String>,
+    pub success: bool,
+}
+
+pub struct LogContextIterator<'a> {
+    root_nodes: &'a [LogContext],
+    current_root_index: usize,
+    current_child_iterator: Option<Box<LogContextIterator<'a>>>,
+}
+
+impl<'a> LogContextIterator<'a> {
+    pub fn new(nodes: &'a [LogContext]) -> Self {
+        LogContextIterator {
+            root_nodes: nodes,
+            current_root_index: 0,
+            current_child_iterator: None,
+        }
+    }
+}
+
+impl<'a> Iterator for LogContextIterator<'a> {
+    type Item = &'a LogContext;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(ref mut child_iter) = self.current_child_iterator {
+            if let Some(child) = child_iter.next() {
+                return Some(child);
+            }
+            // Current child iterator is exhausted.
+            self.current_child_iterator = None;
+        }
+
+        if self.current_root_index < self.root_nodes.len() {
+            let node = &self.root_nodes[self.current_root_index];
+            self.current_root_index += 1;
+
+            if !node.children_nodes.is_empty() {
+                self.current_child_iterator =
+                    Some(Box::new(LogContextIterator::new(&node.children_nodes)));
+            }
+
+            Some(node)
+        } else {
+            None
+        }
+    }
+}
+
+// Compiled once, parse_logs runs for every transaction of a block.
+fn get_parser_re() -> &'static Regex {
+    static PARSER_RE: OnceLock<Regex> = OnceLock::new();
+    PARSER_RE.get_or_init(|| {
+        Regex::new(
+            concat!(
+                r"(^Program (?P<program_id>[1-9A-HJ-NP-Za-km-z]{32,}) invoke \[(?P<depth>\d+)\]$)|",
+                r"(^Program (?P<success_program_id>[1-9A-HJ-NP-Za-km-z]{32,}) success$)|",
+                r"(^Program (?P<failed_program_id>[1-9A-HJ-NP-Za-km-z]{32,}) failed: (?P<failed_message>.+)$)|",
+                r"(^Program (?P<consumed_program_id>[1-9A-HJ-NP-Za-km-z]{32,}) consumed (?P<consumed>\d+) of (?P<total_compute_units>\d+) compute units$)|",
+                r"(^Program data: (?P<data>.+)$)|",
+                r"(^Program log: (?P<log>.+)$)"
+            )
+        ).expect("Failed to compile regex")
+    })
+}
+
+pub fn parse_logs(logs: &[String]) -> Vec<LogContext> {
+    let mut temp_nodes = Vec::new();
+    let mut stack = Vec::new();
+    let parser_re = get_parser_re();
+
+    for log in logs {
+        if let Some(captures) = parser_re.captures(log) {
+            process_log_entry(&captures, &mut temp_nodes, &mut stack);
+        }
+    }
+
+    build_final_result(&temp_nodes)
+}
+
+fn process_log_entry(
+    captures: &Captures,
+    temp_nodes: &mut Vec<LogContext>,
+    stack: &mut Vec<usize>,
+) {
+    if let Some(program_id_capture) = captures.name("program_id") {
+        let depth = captures["depth"].parse::<usize>().unwrap_or_default();
+        let program_id = program_id_capture.as_str().to_string();
+
+        adjust_stack_for_depth(depth, stack);
+
+        let node_index = create_log_context(&program_id, depth, temp_nodes);
+        add_node_to_hierarchy(depth, node_index, stack, temp_nodes);
+    } else {
+        update_existing_log_context(captures, temp_nodes, stack);
+    }
+}
+
+fn adjust_stack_for_depth(depth: usize, stack: &mut Vec<usize>) {
+    while stack.len() >= depth {
+        stack.pop();
+    }
+}
+
+fn create_log_context(program_id: &str, depth: usize, temp_nodes: &mut Vec<LogContext>) -> usize {
+    let node = LogContext {
+        program_id: program_id.to_string(),
+        depth,
+        id: temp_nodes.len(),
+        consumed_units: 0,
+        compute_units: 0,
+        children: Vec::new(),
+        children_nodes: Vec::new(),
+        program_logs: Vec::new(),
+        program_data: Vec::new(),
+        failure_message: None,
+        success: false,
+    };
+
+    temp_nodes.push(node);
+    temp_nodes.len() - 1
+}
+
+fn add_node_to_hierarchy(
+    depth: usize,
+    node_index: usize,
+    stack: &mut Vec<usize>,
+    temp_nodes: &mut [LogContext],
+) {
+    if depth > 1 && !stack.is_empty() {
+        let parent_index = *stack.last().unwrap();
+        temp_nodes[parent_index].children.push(node_index);
+    }
+    stack.push(node_index);
+}
+
+fn update_existing_log_context(
+    captures: &Captures,
+    temp_nodes: &mut [LogContext],
+    stack: &mut Vec<usize>,
+) {
+    if let Some(last_index) = stack.last() {
+        let context = &mut temp_nodes[*last_index];
+        if let Some(consumed_capture) = captures.name("consumed") {
+            let computed_capture = captures.name("total_compute_units");
+            context.consumed_units += consumed_capture
+                .as_str()
+                .parse::<usize>()
+                .unwrap_or_default();
+            context.compute_units += computed_capture
+                .unwrap()
+                .as_str()
+                .parse::<usize>()
+                .unwrap_or_default();
+        }
+        if let Some(log_capture) = captures.name("log") {
+            context.program_logs.push(log_capture.as_str().to_string());
+        }
+        if let Some(data_capture) = captures.name("data") {
+            context.program_data.push(data_capture.as_str().to_string());
+        }
+        if let Some(failed_message_capture) = captures.name("failed_message") {
+            context.failure_message = Some(failed_message_capture.as_str().to_string());
+            stack.pop();
+        }
+        if let Some(success_program_id) = captures.name("success_program_id") {
+            if context.program_id == success_program_id.as_str() {
+                context.success = true;
+                stack.pop();
+            }
+        }
+    }
+}
+
+fn build_final_result(temp_nodes: &[LogContext]) -> Vec<LogContext> {
+    let mut result = Vec::new();
+    for node in temp_nodes.iter().filter(|node| node.depth == 1) {
+        let mut root_node = node.clone();
+        build_hierarchy(&mut root_node, temp_nodes);
+        result.push(root_node);
+    }
+    result
+}
+
+fn build_hierarchy(parent: &mut LogContext, temp_nodes: &[LogContext]) {
+    for &child_index in &parent.children {
+        let mut child_node = temp_nodes[child_index].clone();
+        build_hierarchy(&mut child_node, temp_nodes);
+        parent.children_nodes.push(child_node);
+    }
+    parent.children.clear();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to_logs(messages: &[&str]) -> Vec<String> {
+        messages.iter().map(|message| message.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_successful_invocation_tree() {
+        let log_messages = to_logs(&[
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe invoke [1]",
+            "Program log: Instruction 6: InitializeParimutuelPositionAccount",
+            "Program log: Creating ParimutuelTraderPositionAccount...",
+            "Program log: parimutuel_trader_key 9VnAD3WqihcNqmtNSqEXQrkWncxHLk7uZWxpAVmdAAj6",
+            "Program log: creating parimutuel_trader_position_seeds...",
+            "Program log: derived parimutuel_trader_position_seeds",
+            "Program log: Transfer 2088000 lamports to the new account",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: Allocate space for the account",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: Assign program to the GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: ParimutuelTraderPositionAccount created.",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe consumed 50822 of 800000 compute units",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe success",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe invoke [1]",
+            "Program log: Instruction 5: InitializeTraderAccounts",
+            "Program log: Running validations...",
+            "Program log: Checking protocol token account is_ata",
+            "Program log: Checking settlement token account is_ata",
+            "Program log: Validitions succeeded.",
+            "Program log: Account already exists.",
+            "Program log: Account already exists.",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe consumed 21050 of 749178 compute units",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe success",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe invoke [1]",
+            "Program log: Instruction 9: UpdatePosition",
+            "Program log: Validating...",
+            "Program log: Validations succeeded.",
+            "Program log: parimutuel_trader_entry GjogqXbDsBJWNAiy1ZoEPNMxEM27CXmV9XEx2QCWGyH5 GjogqXbDsBJWNAiy1ZoEPNMxEM27CXmV9XEx2QCWGyH5 - sequence: 1672361357144",
+            "Program log: creating parimutuel_trader_position_seeds...",
+            "Program log: derived parimutuel_trader_entry_seeds",
+            "Program log: Transfer 1746960 lamports to the new account",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: Allocate space for the account",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: Assign program to the GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: Amount: 125000000",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+            "Program log: Instruction: Transfer",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 4645 of 662609 compute units",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program log: Transferred.",
+            "Program log: Size: 125000000",
+            "Program log: Entering Long Position",
+            "Program log: serializing parimutuel_trader_position_account...",
+            "Program log: serializing parimutuel",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe consumed 76880 of 728128 compute units",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe success",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe invoke [1]",
+            "Program log: Instruction 9: UpdatePosition",
+            "Program log: Validating...",
+            "Program log: Validations succeeded.",
+            "Program log: parimutuel_trader_entry EZazx9HvegyksCUJuuurvJ4ehsgpdFaD4KCZCGnHHhzk EZazx9HvegyksCUJuuurvJ4ehsgpdFaD4KCZCGnHHhzk - sequence: 1672361357145",
+            "Program log: creating parimutuel_trader_position_seeds...",
+            "Program log: derived parimutuel_trader_entry_seeds",
+            "Program log: Transfer 1746960 lamports to the new account",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: Allocate space for the account",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: Assign program to the GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program log: Amount: 125000000",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+            "Program log: Instruction: Transfer",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 4645 of 584253 compute units",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program log: Transferred.",
+            "Program log: Size: 125000000",
+            "Program log: Entering Short Position",
+            "Program log: serializing parimutuel_trader_position_account...",
+            "Program log: serializing parimutuel",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe consumed 78354 of 651248 compute units",
+            "Program GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe success"
+        ]);
+
+        let parsed_logs = parse_logs(&log_messages);
+
+        assert_eq!(parsed_logs.len(), 4);
+        assert_eq!(
+            parsed_logs
+                .iter()
+                .map(|root| root.children_nodes.len())
+                .collect::<Vec<_>>(),
+            vec![3, 0, 4, 4]
+        );
+
+        for root in &parsed_logs {
+            assert_eq!(
+                root.program_id,
+                "GUhB2ohrfqWspztgCrQpAmeVFBWmnWYhPcZuwY52WWRe"
+            );
+            assert_eq!(root.depth, 1);
+            assert!(root.success);
+            assert!(root.failure_message.is_none());
+            for child in &root.children_nodes {
+                assert_eq!(child.depth, 2);
+                assert!(child.success);
+                assert!(child.children_nodes.is_empty());
+            }
+        }
+
+        let first = &parsed_logs[0];
+        assert_eq!(first.consumed_units, 50822);
+        assert_eq!(first.compute_units, 800000);
+        assert_eq!(first.program_logs.len(), 9);
+        assert_eq!(
+            first.program_logs[0],
+            "Instruction 6: InitializeParimutuelPositionAccount"
+        );
+        // System program invocations log no compute units.
+        assert_eq!(first.children_nodes[0].consumed_units, 0);
+
+        let transfer = &parsed_logs[2].children_nodes[3];
+        assert_eq!(
+            transfer.program_id,
+            "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA"
+        );
+        assert_eq!(transfer.consumed_units, 4645);
+        assert_eq!(transfer.compute_units, 662609);
+        assert_eq!(transfer.program_logs, vec!["Instruction: Transfer"]);
+        // Logs emitted after the cpi returns belong to the caller.
+        assert_eq!(
+            parsed_logs[2].program_logs.last().map(String::as_str),
+            Some("serializing parimutuel")
+        );
+
+        assert_eq!(parsed_logs[3].consumed_units, 78354);
+        assert_eq!(parsed_logs[3].compute_units, 651248);
+    }
+
+    #[test]
+    fn parses_failed_invocation_tree() {
+        let log_messages = to_logs(&[
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program log: Instruction: Route",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+            "Program log: Instruction: Transfer",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 4645 of 180000 compute units",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program data: 4dd2cHp0aW9u",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc consumed 30000 of 190000 compute units",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc success",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [2]",
+            "Program log: Error: insufficient funds",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA consumed 3000 of 150000 compute units",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA failed: custom program error: 0x1",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 consumed 60000 of 200000 compute units",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 failed: custom program error: 0x1",
+        ]);
+
+        let parsed_logs = parse_logs(&log_messages);
+
+        assert_eq!(parsed_logs.len(), 1);
+        let root = &parsed_logs[0];
+        assert!(!root.success);
+        assert_eq!(
+            root.failure_message.as_deref(),
+            Some("custom program error: 0x1")
+        );
+        assert_eq!(root.consumed_units, 60000);
+        assert_eq!(root.compute_units, 200000);
+        assert_eq!(root.children_nodes.len(), 2);
+
+        let swap = &root.children_nodes[0];
+        assert!(swap.success);
+        assert_eq!(swap.depth, 2);
+        assert_eq!(swap.program_data, vec!["4dd2cHp0aW9u"]);
+        assert_eq!(swap.children_nodes.len(), 1);
+        assert_eq!(swap.children_nodes[0].depth, 3);
+        assert!(swap.children_nodes[0].success);
+
+        let failed_transfer = &root.children_nodes[1];
+        assert!(!failed_transfer.success);
+        assert_eq!(failed_transfer.depth, 2);
+        assert_eq!(
+            failed_transfer.failure_message.as_deref(),
+            Some("custom program error: 0x1")
+        );
+        assert_eq!(
+            failed_transfer.program_logs,
+            vec!["Error: insufficient funds"]
+        );
+    }
+
+    #[test]
+    fn iterates_descendants_in_inner_instruction_order() {
+        let log_messages = to_logs(&[
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc success",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success",
+            "Program ComputeBudget111111111111111111111111111111 invoke [1]",
+            "Program ComputeBudget111111111111111111111111111111 success",
+        ]);
+
+        let parsed_logs = parse_logs(&log_messages);
+
+        assert_eq!(
+            LogContextIterator::new(&parsed_logs)
+                .map(|node| node.program_id.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+                "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+                "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+                "11111111111111111111111111111111",
+                "ComputeBudget111111111111111111111111111111",
+            ]
+        );
+        assert_eq!(
+            LogContextIterator::new(&parsed_logs[0].children_nodes)
+                .map(|node| node.id)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+    }
+}
@@ -1 +1,2 @@
-pub const BUBBLEGUM_PROGRAM_ADDRESS: &str = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY";
\ No newline at end of file
+pub const BUBBLEGUM_PROGRAM_ADDRESS: &str = "BGUMAp9Gq7iTEuizy4pqaxsTyUCBK68MDfK752saRPUY";
+pub const NOOP_PROGRAM_ADDRESS: &str = "noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV";
@@ -0,0 +1,99 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use substreams_solana::pb::sf::solana::r#type::v1::InnerInstruction;
+
+use crate::constants::{BUBBLEGUM_PROGRAM_ADDRESS, NOOP_PROGRAM_ADDRESS};
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct LeafSchemaV1Layout {
+    id: [u8; 32],
+    owner: [u8; 32],
+    delegate: [u8; 32],
+    nonce: u64,
+    dataHash: [u8; 32],
+    creatorHash: [u8; 32],
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct PathNodeLayout {
+    node: [u8; 32],
+    index: u32,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct ChangeLogEventV1Layout {
+    id: [u8; 32],
+    path: Vec<PathNodeLayout>,
+    seq: u64,
+    index: u32,
+}
+
+#[derive(Debug, Default)]
+pub struct LeafEvent {
+    pub merkle_tree: String,
+    pub leaf_index: u64,
+    pub asset_id: String,
+    pub owner: String,
+    pub delegate: String,
+}
+
+// Bubblegum logs the new leaf and spl-account-compression logs the tree change through
+// the noop program right after the instruction, before the next bubblegum instruction.
+pub fn get_leaf_event(
+    inner_instructions: &Vec<InnerInstruction>,
+    start: usize,
+    accounts: &Vec<String>,
+) -> Option<LeafEvent> {
+    let mut leaf_schema: Option<LeafSchemaV1Layout> = None;
+    let mut change_log: Option<ChangeLogEventV1Layout> = None;
+
+    for inner_inst in inner_instructions.iter().skip(start) {
+        let program = &accounts[inner_inst.program_id_index as usize];
+        if program == BUBBLEGUM_PROGRAM_ADDRESS {
+            break;
+        }
+        if program != NOOP_PROGRAM_ADDRESS || inner_inst.data.len() < 3 {
+            continue;
+        }
+
+        let data = &inner_inst.data;
+        match (data[0], data[1]) {
+            // AccountCompressionEvent::ChangeLog(ChangeLogEvent::V1)
+            (0, 0) if data.len() != 169 => {
+                if change_log.is_none() {
+                    change_log = ChangeLogEventV1Layout::deserialize(&mut &data[2..]).ok();
+                }
+            }
+            // AccountCompressionEvent::ApplicationData(V1) wrapping a LeafSchemaEvent
+            (1, 0) if data.len() > 9 && data[6] == 1 && data[7] == 0 && data[8] == 0 => {
+                if leaf_schema.is_none() {
+                    leaf_schema = LeafSchemaV1Layout::deserialize(&mut &data[9..]).ok();
+                }
+            }
+            // Older bubblegum versions log the bare LeafSchema::V1
+            (0, _) if data.len() == 169 => {
+                if leaf_schema.is_none() {
+                    leaf_schema = LeafSchemaV1Layout::deserialize(&mut &data[1..]).ok();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if leaf_schema.is_none() && change_log.is_none() {
+        return None;
+    }
+
+    let mut result = LeafEvent::default();
+    if let Some(schema) = leaf_schema {
+        result.leaf_index = schema.nonce;
+        result.asset_id = bs58::encode(schema.id).into_string();
+        result.owner = bs58::encode(schema.owner).into_string();
+        result.delegate = bs58::encode(schema.delegate).into_string();
+    }
+    if let Some(change_log) = change_log {
+        result.merkle_tree = bs58::encode(change_log.id).into_string();
+        result.leaf_index = change_log.index as u64;
+    }
+
+    Some(result)
+}
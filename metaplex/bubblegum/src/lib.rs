@@ -6,13 +6,16 @@ mod constants;
 mod utils;
 mod pb;
 mod instruction;
+mod leaf_schema;
+mod stores;
 
-use pb::sf::solana::block_meta::v1::{
+use pb::sf::solana::bubblegum::v1::{
     Arg, BubblegumMeta, Collection, Creator, Message, MetadataArgs, Uses, Metadata, Output,
 };
 use substreams::log;
 use substreams_solana::pb::sf::solana::r#type::v1::Block;
 use instruction::{parse_instruction, Instruction};
+use leaf_schema::get_leaf_event;
 use utils::convert_to_date;
 use substreams::store::{StoreGet, StoreGetArray};
 
@@ -26,6 +29,10 @@ fn map_block(block: Block, address_lookup_table_store: StoreGetArray<String>) ->
     for trx in block.transactions_owned() {
         if let Some(transaction) = trx.transaction {
             let meta = trx.meta.unwrap();
+            // A failed transaction changes no tree, whatever its instructions say.
+            if meta.err.is_some() {
+                continue;
+            }
             let msg = transaction.message.unwrap();
             let mut accounts = vec![];
             let mut writable_accounts = vec![];
@@ -62,6 +69,15 @@ fn map_block(block: Block, address_lookup_table_store: StoreGetArray<String>) ->
                 }
                 
 
+                let leaf_event = meta
+                    .inner_instructions
+                    .iter()
+                    .find(|inner_instruction| inner_instruction.index == idx as u32)
+                    .and_then(|inner_instruction| {
+                        get_leaf_event(&inner_instruction.instructions, 0, &accounts)
+                    })
+                    .unwrap_or_default();
+
                 data.push(BubblegumMeta {
                     block_date: convert_to_date(timestamp),
                     block_time: timestamp,
@@ -71,7 +87,12 @@ fn map_block(block: Block, address_lookup_table_store: StoreGetArray<String>) ->
                     instruction_index: inst.program_id_index,
                     is_inner_instruction: false,
                     inner_instruction_index: 0,
-                    arg: Some(get_arg(inst.data, &inst.accounts, &accounts))
+                    arg: Some(get_arg(inst.data, &inst.accounts, &accounts)),
+                    merkle_tree: leaf_event.merkle_tree,
+                    leaf_index: leaf_event.leaf_index,
+                    asset_id: leaf_event.asset_id,
+                    leaf_owner: leaf_event.owner,
+                    leaf_delegate: leaf_event.delegate,
                 });
 
                 meta.inner_instructions
@@ -80,9 +101,17 @@ fn map_block(block: Block, address_lookup_table_store: StoreGetArray<String>) ->
                     .for_each(|inner_instruction| {
                         inner_instruction.instructions
                         .iter()
-                        .for_each(|inner_inst| {
+                        .enumerate()
+                        .for_each(|(inner_idx, inner_inst)| {
                             let program = &accounts[inner_inst.program_id_index as usize];
                             if program == constants::BUBBLEGUM_PROGRAM_ADDRESS {
+                                let leaf_event = get_leaf_event(
+                                    &inner_instruction.instructions,
+                                    inner_idx + 1,
+                                    &accounts,
+                                )
+                                .unwrap_or_default();
+
                                 data.push(BubblegumMeta {
                                     block_date: convert_to_date(timestamp),
                                     block_time: timestamp,
@@ -92,7 +121,12 @@ fn map_block(block: Block, address_lookup_table_store: StoreGetArray<String>) ->
                                     instruction_index: inst.program_id_index,
                                     is_inner_instruction: true,
                                     inner_instruction_index: inner_inst.program_id_index,
-                                    arg: Some(get_arg(inner_inst.data.clone(), &inner_inst.accounts, &accounts))
+                                    arg: Some(get_arg(inner_inst.data.clone(), &inner_inst.accounts, &accounts)),
+                                    merkle_tree: leaf_event.merkle_tree,
+                                    leaf_index: leaf_event.leaf_index,
+                                    asset_id: leaf_event.asset_id,
+                                    leaf_owner: leaf_event.owner,
+                                    leaf_delegate: leaf_event.delegate,
                                 });
                             }
                         })
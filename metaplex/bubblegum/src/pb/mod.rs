@@ -10,11 +10,11 @@ pub mod addresslookuptables {
 }
 pub mod sf {
     pub mod solana {
-        pub mod block_meta {
-            // @@protoc_insertion_point(attribute:sf.solana.block_meta.v1)
+        pub mod bubblegum {
+            // @@protoc_insertion_point(attribute:sf.solana.bubblegum.v1)
             pub mod v1 {
-                include!("sf.solana.block_meta.v1.rs");
-                // @@protoc_insertion_point(sf.solana.block_meta.v1)
+                include!("sf.solana.bubblegum.v1.rs");
+                // @@protoc_insertion_point(sf.solana.bubblegum.v1)
             }
         }
     }
@@ -156,6 +156,16 @@ pub struct BubblegumMeta {
     pub inner_instruction_index: u32,
     #[prost(message, optional, tag="10")]
     pub arg: ::core::option::Option<Arg>,
+    #[prost(string, tag="11")]
+    pub merkle_tree: ::prost::alloc::string::String,
+    #[prost(uint64, tag="12")]
+    pub leaf_index: u64,
+    #[prost(string, tag="13")]
+    pub asset_id: ::prost::alloc::string::String,
+    #[prost(string, tag="14")]
+    pub leaf_owner: ::prost::alloc::string::String,
+    #[prost(string, tag="15")]
+    pub leaf_delegate: ::prost::alloc::string::String,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -163,4 +173,42 @@ pub struct Output {
     #[prost(message, repeated, tag="1")]
     pub data: ::prost::alloc::vec::Vec<BubblegumMeta>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CnftCollection {
+    #[prost(string, tag="1")]
+    pub merkle_tree: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub leaf_index: u64,
+    #[prost(string, tag="3")]
+    pub asset_id: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub collection_mint: ::prost::alloc::string::String,
+    #[prost(bool, tag="5")]
+    pub verified: bool,
+    #[prost(int64, tag="6")]
+    pub updated_at: i64,
+    #[prost(string, tag="7")]
+    pub tx_id: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CnftOwner {
+    #[prost(string, tag="1")]
+    pub merkle_tree: ::prost::alloc::string::String,
+    #[prost(uint64, tag="2")]
+    pub leaf_index: u64,
+    #[prost(string, tag="3")]
+    pub asset_id: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub delegate: ::prost::alloc::string::String,
+    #[prost(int64, tag="6")]
+    pub updated_at: i64,
+    #[prost(string, tag="7")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub instruction_type: ::prost::alloc::string::String,
+}
 // @@protoc_insertion_point(module)
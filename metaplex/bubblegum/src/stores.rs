@@ -0,0 +1,98 @@
+use substreams::store::{StoreNew, StoreSet, StoreSetProto};
+
+use crate::pb::sf::solana::bubblegum::v1::{BubblegumMeta, CnftCollection, CnftOwner, Output};
+
+#[substreams::handlers::store]
+fn store_cnft_collections(output: Output, store: StoreSetProto<CnftCollection>) {
+    for (ordinal, meta) in output.data.iter().enumerate() {
+        if meta.merkle_tree.is_empty() {
+            continue;
+        }
+
+        let (collection_mint, verified) = match get_collection(meta) {
+            Some(collection) => collection,
+            None => continue,
+        };
+
+        store.set(
+            ordinal as u64,
+            format!("{}:{}", meta.merkle_tree, meta.leaf_index),
+            &CnftCollection {
+                merkle_tree: meta.merkle_tree.to_string(),
+                leaf_index: meta.leaf_index,
+                asset_id: meta.asset_id.to_string(),
+                collection_mint,
+                verified,
+                updated_at: meta.block_time,
+                tx_id: meta.tx_id.to_string(),
+            },
+        );
+    }
+}
+
+#[substreams::handlers::store]
+fn store_cnft_owners(output: Output, store: StoreSetProto<CnftOwner>) {
+    for (ordinal, meta) in output.data.iter().enumerate() {
+        if meta.merkle_tree.is_empty() {
+            continue;
+        }
+
+        let instruction_type = meta
+            .arg
+            .as_ref()
+            .map(|arg| arg.instruction_type.to_string())
+            .unwrap_or_default();
+
+        // Burn and Redeem replace the leaf with an empty node, the asset is kept without
+        // an owner (a redeemed one lives on as a regular NFT). Otherwise only instructions
+        // that log a new leaf schema change the ownership.
+        let is_removed = instruction_type == "Burn" || instruction_type == "Redeem";
+        if !is_removed && meta.asset_id.is_empty() {
+            continue;
+        }
+
+        let (owner, delegate) = if is_removed {
+            (String::new(), String::new())
+        } else {
+            (meta.leaf_owner.to_string(), meta.leaf_delegate.to_string())
+        };
+
+        store.set(
+            ordinal as u64,
+            format!("{}:{}", meta.merkle_tree, meta.leaf_index),
+            &CnftOwner {
+                merkle_tree: meta.merkle_tree.to_string(),
+                leaf_index: meta.leaf_index,
+                asset_id: meta.asset_id.to_string(),
+                owner,
+                delegate,
+                updated_at: meta.block_time,
+                tx_id: meta.tx_id.to_string(),
+                instruction_type,
+            },
+        );
+    }
+}
+
+fn get_collection(meta: &BubblegumMeta) -> Option<(String, bool)> {
+    let arg = meta.arg.as_ref()?;
+    let message_collection = arg
+        .message
+        .as_ref()
+        .and_then(|message| message.collection.as_ref())
+        .map(|collection| collection.key.to_string());
+
+    match arg.instruction_type.as_str() {
+        // Minting into a collection verifies it in the same instruction.
+        "MintToCollectionV1" => arg
+            .metadata_args
+            .as_ref()
+            .and_then(|metadata_args| metadata_args.collection.as_ref())
+            .map(|collection| (collection.key.to_string(), true)),
+        "MintV1" => message_collection.map(|key| (key, false)),
+        "VerifyCollection" => message_collection.map(|key| (key, true)),
+        "UnverifyCollection" => message_collection.map(|key| (key, false)),
+        "SetAndVerifyCollection" => Some((arg.collection.to_string(), true)),
+        _ => None,
+    }
+}
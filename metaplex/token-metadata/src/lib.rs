@@ -7,6 +7,7 @@ mod instructions;
 mod pb;
 mod prepare_arg;
 mod prepare_input_accounts;
+mod stores;
 mod utils;
 
 use pb::sf::solana::block_meta::v1::{Arg, Output, TokenMetadataMeta};
@@ -29,6 +30,10 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
         if let Some(transaction) = trx.transaction {
             let msg = transaction.message.unwrap();
             let meta = trx.meta.unwrap();
+            // A failed transaction changes no metadata, whatever its instructions say.
+            if meta.err.is_some() {
+                continue;
+            }
 
             for (idx, inst) in msg.instructions.into_iter().enumerate() {
                 let program = &accounts[inst.program_id_index as usize];
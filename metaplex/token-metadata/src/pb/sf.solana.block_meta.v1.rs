@@ -689,4 +689,20 @@ pub struct Output {
     #[prost(message, repeated, tag="1")]
     pub data: ::prost::alloc::vec::Vec<TokenMetadataMeta>,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectionAttribution {
+    #[prost(string, required, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, required, tag="2")]
+    pub metadata: ::prost::alloc::string::String,
+    #[prost(string, required, tag="3")]
+    pub collection_mint: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="4")]
+    pub verified: bool,
+    #[prost(int64, required, tag="5")]
+    pub updated_at: i64,
+    #[prost(string, required, tag="6")]
+    pub tx_id: ::prost::alloc::string::String,
+}
 // @@protoc_insertion_point(module)
@@ -0,0 +1,111 @@
+use substreams::store::{
+    StoreGet, StoreGetString, StoreNew, StoreSet, StoreSetProto, StoreSetString,
+};
+
+use crate::pb::sf::solana::block_meta::v1::{CollectionAttribution, Output, TokenMetadataMeta};
+
+#[substreams::handlers::store]
+fn store_metadata_mints(output: Output, store: StoreSetString) {
+    for (ordinal, meta) in output.data.iter().enumerate() {
+        match meta.instruction_type.as_str() {
+            "CreateMetadataAccount"
+            | "CreateMetadataAccountV2"
+            | "CreateMetadataAccountV3"
+            | "Create" => {
+                let metadata = meta.input_accounts.metadata.clone().unwrap_or_default();
+                let mint = meta.input_accounts.mint.clone().unwrap_or_default();
+                if !metadata.is_empty() && !mint.is_empty() {
+                    store.set(ordinal as u64, metadata, &mint);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_collections(
+    output: Output,
+    metadata_mints_store: StoreGetString,
+    store: StoreSetProto<CollectionAttribution>,
+) {
+    for (ordinal, meta) in output.data.iter().enumerate() {
+        let (collection_mint, verified) = match get_collection(meta) {
+            Some(collection) => collection,
+            None => continue,
+        };
+        if collection_mint.is_empty() {
+            continue;
+        }
+
+        // Verification instructions only pass the metadata account, the mint comes from
+        // the metadata creation seen earlier.
+        let metadata = meta.input_accounts.metadata.clone().unwrap_or_default();
+        let mint = match meta.input_accounts.mint.clone() {
+            Some(mint) if !mint.is_empty() => mint,
+            _ => match metadata_mints_store.get_last(&metadata) {
+                Some(mint) => mint,
+                None => continue,
+            },
+        };
+
+        store.set(
+            ordinal as u64,
+            &mint,
+            &CollectionAttribution {
+                mint: mint.to_string(),
+                metadata,
+                collection_mint,
+                verified,
+                updated_at: meta.block_time,
+                tx_id: meta.tx_id.to_string(),
+            },
+        );
+    }
+}
+
+// The program rejects a verified collection at creation, it only becomes verified with a
+// successful verification signed by the collection authority.
+fn get_collection(meta: &TokenMetadataMeta) -> Option<(String, bool)> {
+    let args = &meta.args;
+    let collection_mint = meta
+        .input_accounts
+        .collection_mint
+        .clone()
+        .unwrap_or_default();
+
+    match meta.instruction_type.as_str() {
+        "CreateMetadataAccountV2" => args
+            .create_metadata_account_args_v2
+            .as_ref()
+            .and_then(|create_args| create_args.data.collection.as_ref())
+            .map(|collection| (collection.key.to_string(), false)),
+        "CreateMetadataAccountV3" => args
+            .create_metadata_account_args_v3
+            .as_ref()
+            .and_then(|create_args| create_args.data.collection.as_ref())
+            .map(|collection| (collection.key.to_string(), false)),
+        "Create" => args
+            .create_args
+            .as_ref()
+            .and_then(|create_args| create_args.asset_data.collection.as_ref())
+            .map(|collection| (collection.key.to_string(), false)),
+        "VerifyCollection"
+        | "VerifySizedCollectionItem"
+        | "SetAndVerifyCollection"
+        | "SetAndVerifySizedCollectionItem" => Some((collection_mint, true)),
+        "UnverifyCollection" | "UnverifySizedCollectionItem" => Some((collection_mint, false)),
+        "Verify" | "Unverify" => {
+            let is_collection = args
+                .verification_args
+                .as_ref()
+                .map_or(false, |verification| verification.name == "CollectionV1");
+            if is_collection {
+                Some((collection_mint, meta.instruction_type == "Verify"))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
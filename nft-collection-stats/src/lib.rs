@@ -0,0 +1,186 @@
+mod pb;
+mod sales;
+mod utils;
+
+use pb::sf::solana::cnft::trades::v1::Output as CnftOutput;
+use pb::sf::solana::nft::collection::stats::v1::{CollectionDailyStats, Output};
+use pb::sf::solana::nft::trades::v1::Output as NftOutput;
+use sales::{get_sales, Sale};
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::store::{
+    DeltaString, Deltas, StoreAdd, StoreAddFloat64, StoreAddInt64, StoreDelete, StoreGet,
+    StoreGetFloat64, StoreGetInt64, StoreMin, StoreMinFloat64, StoreNew, StoreSetIfNotExists,
+    StoreSetIfNotExistsString,
+};
+use utils::convert_to_date;
+
+// Number of days, today included, the rolling floor looks back over.
+const FLOOR_WINDOW_DAYS: u32 = 7;
+const SECONDS_PER_DAY: i64 = 86_400;
+
+#[substreams::handlers::store]
+fn store_collection_traders(
+    clock: Clock,
+    nft_trades: NftOutput,
+    cnft_trades: CnftOutput,
+    store: StoreSetIfNotExistsString,
+) {
+    let expired_date = get_expired_date(&clock);
+    store.delete_prefix(0, &format!("buyer:{}:", expired_date));
+    store.delete_prefix(0, &format!("seller:{}:", expired_date));
+
+    for sale in get_sales(&nft_trades, &cnft_trades) {
+        let key = get_key(&sale);
+        store.set_if_not_exists(
+            sale.ordinal,
+            format!("buyer:{}:{}", key, sale.buyer),
+            &sale.block_time.to_string(),
+        );
+        store.set_if_not_exists(
+            sale.ordinal,
+            format!("seller:{}:{}", key, sale.seller),
+            &sale.block_time.to_string(),
+        );
+    }
+}
+
+#[substreams::handlers::store]
+fn store_collection_volume(
+    clock: Clock,
+    nft_trades: NftOutput,
+    cnft_trades: CnftOutput,
+    store: StoreAddFloat64,
+) {
+    store.delete_prefix(0, &format!("{}:", get_expired_date(&clock)));
+
+    for sale in get_sales(&nft_trades, &cnft_trades) {
+        store.add(sale.ordinal, get_key(&sale), sale.amount);
+    }
+}
+
+#[substreams::handlers::store]
+fn store_collection_counts(
+    clock: Clock,
+    nft_trades: NftOutput,
+    cnft_trades: CnftOutput,
+    traders_deltas: Deltas<DeltaString>,
+    store: StoreAddInt64,
+) {
+    let expired_date = get_expired_date(&clock);
+    for name in ["sales", "buyers", "sellers"] {
+        store.delete_prefix(0, &format!("{}:{}:", name, expired_date));
+    }
+
+    for sale in get_sales(&nft_trades, &cnft_trades) {
+        store.add(sale.ordinal, format!("sales:{}", get_key(&sale)), 1);
+    }
+
+    // A trader delta is only emitted the first time a wallet trades the collection that day.
+    for delta in traders_deltas.deltas {
+        if delta.operation != Operation::Create {
+            continue;
+        }
+        let parts: Vec<&str> = delta.key.splitn(4, ':').collect();
+        if parts.len() != 4 {
+            continue;
+        }
+        let key = format!("{}:{}", parts[1], parts[2]);
+        match parts[0] {
+            "buyer" => store.add(delta.ordinal, format!("buyers:{}", key), 1),
+            "seller" => store.add(delta.ordinal, format!("sellers:{}", key), 1),
+            _ => {}
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_collection_floor(
+    clock: Clock,
+    nft_trades: NftOutput,
+    cnft_trades: CnftOutput,
+    store: StoreMinFloat64,
+) {
+    store.delete_prefix(0, &format!("{}:", get_expired_date(&clock)));
+
+    for sale in get_sales(&nft_trades, &cnft_trades) {
+        store.min(sale.ordinal, get_key(&sale), sale.amount);
+    }
+}
+
+#[substreams::handlers::map]
+fn map_collection_daily_stats(
+    nft_trades: NftOutput,
+    cnft_trades: CnftOutput,
+    volume_store: StoreGetFloat64,
+    counts_store: StoreGetInt64,
+    floor_store: StoreGetFloat64,
+) -> Result<Output, substreams::errors::Error> {
+    let sales = get_sales(&nft_trades, &cnft_trades);
+
+    // One row per collection and day traded in this block, with the totals so far.
+    let mut latest: Vec<&Sale> = vec![];
+    for sale in sales.iter() {
+        let position = latest
+            .iter()
+            .position(|other| get_key(other) == get_key(sale));
+        match position {
+            Some(position) => latest[position] = sale,
+            None => latest.push(sale),
+        }
+    }
+
+    let mut data: Vec<CollectionDailyStats> = vec![];
+    for sale in latest {
+        let key = get_key(sale);
+        let get_count = |name: &str| {
+            counts_store
+                .get_last(format!("{}:{}", name, key))
+                .unwrap_or(0)
+        };
+
+        data.push(CollectionDailyStats {
+            block_date: sale.block_date.to_string(),
+            block_time: sale.block_time,
+            block_slot: sale.block_slot,
+            collection_mint: sale.collection_mint.to_string(),
+            volume: volume_store.get_last(&key).unwrap_or(0.0),
+            sale_count: get_count("sales"),
+            unique_buyers: get_count("buyers"),
+            unique_sellers: get_count("sellers"),
+            floor_price: floor_store.get_last(&key).unwrap_or(0.0),
+            rolling_floor_price: get_rolling_floor(sale, &floor_store),
+            floor_window_days: FLOOR_WINDOW_DAYS,
+        });
+    }
+
+    Ok(Output { data })
+}
+
+// Lowest daily floor over the window ending on the sale's day.
+fn get_rolling_floor(sale: &Sale, floor_store: &StoreGetFloat64) -> f64 {
+    (0..FLOOR_WINDOW_DAYS as i64)
+        .filter_map(|days| {
+            let block_date = convert_to_date(sale.block_time - days * SECONDS_PER_DAY);
+            floor_store.get_last(format!("{}:{}", block_date, sale.collection_mint))
+        })
+        .fold(None, |floor: Option<f64>, price| match floor {
+            Some(floor) if floor <= price => Some(floor),
+            _ => Some(price),
+        })
+        .unwrap_or(0.0)
+}
+
+// Per-day keys are dropped on every block once their day has left the rolling window,
+// nothing reads them past it.
+fn get_expired_date(clock: &Clock) -> String {
+    let timestamp = clock
+        .timestamp
+        .as_ref()
+        .map_or(0, |timestamp| timestamp.seconds);
+    convert_to_date(timestamp - FLOOR_WINDOW_DAYS as i64 * SECONDS_PER_DAY)
+}
+
+fn get_key(sale: &Sale) -> String {
+    format!("{}:{}", sale.block_date, sale.collection_mint)
+}
@@ -0,0 +1,32 @@
+// @generated
+pub mod sf {
+    pub mod solana {
+        pub mod cnft {
+            pub mod trades {
+                // @@protoc_insertion_point(attribute:sf.solana.cnft.trades.v1)
+                pub mod v1 {
+                    include!("../../../cnft-trades/src/pb/sf.solana.cnft.trades.v1.rs");
+                    // @@protoc_insertion_point(sf.solana.cnft.trades.v1)
+                }
+            }
+        }
+        pub mod nft {
+            pub mod collection {
+                pub mod stats {
+                    // @@protoc_insertion_point(attribute:sf.solana.nft.collection.stats.v1)
+                    pub mod v1 {
+                        include!("sf.solana.nft.collection.stats.v1.rs");
+                        // @@protoc_insertion_point(sf.solana.nft.collection.stats.v1)
+                    }
+                }
+            }
+            pub mod trades {
+                // @@protoc_insertion_point(attribute:sf.solana.nft.trades.v1)
+                pub mod v1 {
+                    include!("../../../nft-trades/src/pb/sf.solana.nft.trades.v1.rs");
+                    // @@protoc_insertion_point(sf.solana.nft.trades.v1)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,34 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Output {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<CollectionDailyStats>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectionDailyStats {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="2")]
+    pub block_time: i64,
+    #[prost(uint64, required, tag="3")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="4")]
+    pub collection_mint: ::prost::alloc::string::String,
+    #[prost(double, required, tag="5")]
+    pub volume: f64,
+    #[prost(int64, required, tag="6")]
+    pub sale_count: i64,
+    #[prost(int64, required, tag="7")]
+    pub unique_buyers: i64,
+    #[prost(int64, required, tag="8")]
+    pub unique_sellers: i64,
+    #[prost(double, required, tag="9")]
+    pub floor_price: f64,
+    #[prost(double, required, tag="10")]
+    pub rolling_floor_price: f64,
+    #[prost(uint32, required, tag="11")]
+    pub floor_window_days: u32,
+}
+// @@protoc_insertion_point(module)
@@ -0,0 +1,62 @@
+use crate::pb::sf::solana::cnft::trades::v1::Output as CnftOutput;
+use crate::pb::sf::solana::nft::trades::v1::Output as NftOutput;
+
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+#[derive(Debug, Default)]
+pub struct Sale {
+    pub ordinal: u64,
+    pub block_date: String,
+    pub block_time: i64,
+    pub block_slot: u64,
+    pub collection_mint: String,
+    pub buyer: String,
+    pub seller: String,
+    pub amount: f64,
+}
+
+// Both trade outputs report amounts in lamports, only successful SOL denominated sales
+// with a known collection are aggregated. Failed transactions still decode, with the
+// amounts of their instruction args. Ordinals follow the nft trades then the cnft trades.
+pub fn get_sales(nft_trades: &NftOutput, cnft_trades: &CnftOutput) -> Vec<Sale> {
+    let nft_sales = nft_trades
+        .data
+        .iter()
+        .filter(|td| td.success && !td.parse_failed && td.currency_mint == WSOL_MINT)
+        .map(|td| Sale {
+            ordinal: 0,
+            block_date: td.block_date.to_string(),
+            block_time: td.block_time,
+            block_slot: td.block_slot,
+            collection_mint: td.collection_mint.to_string(),
+            buyer: td.buyer.to_string(),
+            seller: td.seller.to_string(),
+            amount: td.amount / LAMPORTS_PER_SOL,
+        });
+
+    let cnft_sales = cnft_trades
+        .data
+        .iter()
+        .filter(|td| td.success && td.currency == "SOL")
+        .map(|td| Sale {
+            ordinal: 0,
+            block_date: td.block_date.to_string(),
+            block_time: td.block_time,
+            block_slot: td.block_slot,
+            collection_mint: td.collection_mint.to_string(),
+            buyer: td.buyer.to_string(),
+            seller: td.seller.to_string(),
+            amount: td.amount / LAMPORTS_PER_SOL,
+        });
+
+    nft_sales
+        .chain(cnft_sales)
+        .filter(|sale| !sale.collection_mint.is_empty() && sale.amount > 0.0)
+        .enumerate()
+        .map(|(ordinal, sale)| Sale {
+            ordinal: ordinal as u64,
+            ..sale
+        })
+        .collect()
+}
@@ -0,0 +1,9 @@
+extern crate chrono;
+use chrono::prelude::*;
+
+pub fn convert_to_date(ts: i64) -> String {
+    let nt = NaiveDateTime::from_timestamp_opt(ts, 0);
+    let dt: DateTime<Utc> = DateTime::from_naive_utc_and_offset(nt.unwrap(), Utc);
+    let res = dt.format("%Y-%m-%d");
+    return res.to_string();
+}
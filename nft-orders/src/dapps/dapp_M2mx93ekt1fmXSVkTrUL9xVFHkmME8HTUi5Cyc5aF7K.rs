@@ -0,0 +1,112 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::pb::sf::solana::nft::orders::v1::OrderData;
+use crate::utils::{get_discriminator, WSOL_MINT};
+
+const SELL_DISCRIMINATOR: u64 = 12502976635542562355;
+const CANCEL_SELL_DISCRIMINATOR: u64 = 5453682830867613382;
+const BUY_V2_DISCRIMINATOR: u64 = 4455121504214849464;
+const CANCEL_BUY_DISCRIMINATOR: u64 = 16852664989774007534;
+const DEPOSIT_DISCRIMINATOR: u64 = 13182846803881894898;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct SellLayout {
+    sellerStateBump: u8,
+    programAsSignerBump: u8,
+    buyerPrice: u64,
+    tokenSize: u64,
+    sellerStateExpiry: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct CancelLayout {
+    buyerPrice: u64,
+    tokenSize: u64,
+    stateExpiry: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct BuyV2Layout {
+    buyerPrice: u64,
+    tokenSize: u64,
+    buyerStateExpiry: i64,
+    buyerCreatorRoyaltyBp: u16,
+    extraArgs: Vec<u8>,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct DepositLayout {
+    escrowPaymentBump: u8,
+    amount: u64,
+}
+
+pub fn parse_order_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<OrderData> {
+    let (discriminator, rest) = get_discriminator(&bytes_stream);
+
+    let mut order_data = OrderData::default();
+    order_data.platform = "magiceden".to_string();
+    order_data.currency_mint = WSOL_MINT.to_string();
+    order_data.maker = input_accounts.get(0)?.to_string();
+
+    match discriminator {
+        SELL_DISCRIMINATOR => {
+            let args = SellLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "Sell".to_string();
+            order_data.category = "list".to_string();
+            order_data.side = "sell".to_string();
+            order_data.mint = input_accounts.get(4)?.to_string();
+            order_data.pool = input_accounts.get(8)?.to_string();
+            order_data.price = args.buyerPrice as f64;
+            order_data.quantity = args.tokenSize;
+            order_data.expiry = args.sellerStateExpiry;
+        }
+        CANCEL_SELL_DISCRIMINATOR => {
+            let args = CancelLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "CancelSell".to_string();
+            order_data.category = "delist".to_string();
+            order_data.side = "sell".to_string();
+            order_data.mint = input_accounts.get(3)?.to_string();
+            order_data.pool = input_accounts.get(6)?.to_string();
+            order_data.price = args.buyerPrice as f64;
+            order_data.quantity = args.tokenSize;
+            order_data.expiry = args.stateExpiry;
+        }
+        BUY_V2_DISCRIMINATOR => {
+            let args = BuyV2Layout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "BuyV2".to_string();
+            order_data.category = "bid".to_string();
+            order_data.side = "buy".to_string();
+            order_data.mint = input_accounts.get(2)?.to_string();
+            order_data.pool = input_accounts.get(7)?.to_string();
+            order_data.price = args.buyerPrice as f64;
+            order_data.quantity = args.tokenSize;
+            order_data.expiry = args.buyerStateExpiry;
+        }
+        CANCEL_BUY_DISCRIMINATOR => {
+            let args = CancelLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "CancelBuy".to_string();
+            order_data.category = "cancel_bid".to_string();
+            order_data.side = "buy".to_string();
+            order_data.mint = input_accounts.get(2)?.to_string();
+            order_data.pool = input_accounts.get(5)?.to_string();
+            order_data.price = args.buyerPrice as f64;
+            order_data.quantity = args.tokenSize;
+            order_data.expiry = args.stateExpiry;
+        }
+        DEPOSIT_DISCRIMINATOR => {
+            // Bidding wallet top-up, bids are placed against this escrow.
+            let args = DepositLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "Deposit".to_string();
+            order_data.category = "deposit".to_string();
+            order_data.side = "buy".to_string();
+            order_data.pool = input_accounts.get(2)?.to_string();
+            order_data.amount = args.amount as f64;
+        }
+        _ => return None,
+    }
+
+    Some(order_data)
+}
@@ -0,0 +1,109 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::pb::sf::solana::nft::orders::v1::OrderData;
+use crate::utils::{get_discriminator, WSOL_MINT};
+
+const LIST_DISCRIMINATOR: u64 = 2775388424495017526;
+const DELIST_DISCRIMINATOR: u64 = 2235101991964346423;
+const EDIT_SINGLE_LISTING_DISCRIMINATOR: u64 = 11966830706244200024;
+const DEPOSIT_SOL_DISCRIMINATOR: u64 = 14427452369422668140;
+const WITHDRAW_SOL_DISCRIMINATOR: u64 = 2750161437015573393;
+const DEPOSIT_NFT_DISCRIMINATOR: u64 = 7291338300701598301;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct ListLayout {
+    price: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct PoolConfigLayout {
+    poolType: u8,
+    curveType: u8,
+    startingPrice: u64,
+    delta: u64,
+    mmCompoundFees: bool,
+    mmFeeBps: u16,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct DepositWithdrawSolLayout {
+    config: PoolConfigLayout,
+    lamports: u64,
+}
+
+pub fn parse_order_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
+) -> Option<OrderData> {
+    let (discriminator, rest) = get_discriminator(&bytes_stream);
+
+    let mut order_data = OrderData::default();
+    order_data.platform = "tensorswap".to_string();
+    order_data.currency_mint = WSOL_MINT.to_string();
+
+    match discriminator {
+        LIST_DISCRIMINATOR => {
+            let args = ListLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "List".to_string();
+            order_data.category = "list".to_string();
+            order_data.side = "sell".to_string();
+            order_data.mint = input_accounts.get(2)?.to_string();
+            order_data.pool = input_accounts.get(4)?.to_string();
+            order_data.maker = input_accounts.get(6)?.to_string();
+            order_data.price = args.price as f64;
+            order_data.quantity = 1;
+        }
+        DELIST_DISCRIMINATOR => {
+            order_data.instruction_type = "Delist".to_string();
+            order_data.category = "delist".to_string();
+            order_data.side = "sell".to_string();
+            order_data.mint = input_accounts.get(2)?.to_string();
+            order_data.pool = input_accounts.get(4)?.to_string();
+            order_data.maker = input_accounts.get(6)?.to_string();
+            order_data.quantity = 1;
+        }
+        EDIT_SINGLE_LISTING_DISCRIMINATOR => {
+            let args = ListLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "EditSingleListing".to_string();
+            order_data.category = "list".to_string();
+            order_data.side = "sell".to_string();
+            order_data.pool = input_accounts.get(1)?.to_string();
+            order_data.mint = input_accounts.get(2)?.to_string();
+            order_data.maker = input_accounts.get(3)?.to_string();
+            order_data.price = args.price as f64;
+            order_data.quantity = 1;
+        }
+        DEPOSIT_SOL_DISCRIMINATOR | WITHDRAW_SOL_DISCRIMINATOR => {
+            let args = DepositWithdrawSolLayout::deserialize(&mut &rest[..]).ok()?;
+            if discriminator == DEPOSIT_SOL_DISCRIMINATOR {
+                order_data.instruction_type = "DepositSol".to_string();
+                order_data.category = "deposit".to_string();
+            } else {
+                order_data.instruction_type = "WithdrawSol".to_string();
+                order_data.category = "withdraw".to_string();
+            }
+            order_data.side = "buy".to_string();
+            order_data.pool = input_accounts.get(1)?.to_string();
+            order_data.maker = input_accounts.get(3)?.to_string();
+            order_data.price = args.config.startingPrice as f64;
+            order_data.amount = args.lamports as f64;
+        }
+        DEPOSIT_NFT_DISCRIMINATOR => {
+            let config = PoolConfigLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "DepositNft".to_string();
+            order_data.category = "deposit".to_string();
+            order_data.side = "sell".to_string();
+            order_data.pool = input_accounts.get(1)?.to_string();
+            order_data.mint = input_accounts.get(4)?.to_string();
+            order_data.maker = input_accounts.get(8)?.to_string();
+            order_data.price = config.startingPrice as f64;
+            order_data.quantity = 1;
+        }
+        _ => return None,
+    }
+
+    Some(order_data)
+}
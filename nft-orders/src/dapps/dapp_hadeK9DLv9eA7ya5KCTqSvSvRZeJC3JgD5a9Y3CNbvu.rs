@@ -0,0 +1,73 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use substreams_solana::pb::sf::solana::r#type::v1::TokenBalance;
+
+use crate::pb::sf::solana::nft::orders::v1::OrderData;
+use crate::utils::{get_discriminator, get_moved_mint, get_sol_balance_change, WSOL_MINT};
+
+const DEPOSIT_SOL_TO_PAIR_DISCRIMINATOR: u64 = 4918988362539893685;
+const WITHDRAW_SOL_FROM_PAIR_DISCRIMINATOR: u64 = 10845745878821997717;
+const DEPOSIT_NFT_TO_PAIR_DISCRIMINATOR: u64 = 4550730410360117133;
+const WITHDRAW_NFT_FROM_PAIR_DISCRIMINATOR: u64 = 8477383296086602100;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct SolToPairLayout {
+    amountOfOrders: u32,
+}
+
+pub fn parse_order_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
+    pre_token_balances: &Vec<TokenBalance>,
+    post_token_balances: &Vec<TokenBalance>,
+) -> Option<OrderData> {
+    let (discriminator, rest) = get_discriminator(&bytes_stream);
+
+    let mut order_data = OrderData::default();
+    order_data.platform = "hadeswap".to_string();
+    order_data.currency_mint = WSOL_MINT.to_string();
+    order_data.pool = input_accounts.get(0)?.to_string();
+    order_data.maker = input_accounts.get(2)?.to_string();
+
+    match discriminator {
+        DEPOSIT_SOL_TO_PAIR_DISCRIMINATOR | WITHDRAW_SOL_FROM_PAIR_DISCRIMINATOR => {
+            let args = SolToPairLayout::deserialize(&mut &rest[..]).ok()?;
+            // The pair prices orders on its curve, the lamports moved are read from the vault.
+            let vault_change = get_sol_balance_change(
+                input_accounts.get(3)?,
+                accounts,
+                pre_balances,
+                post_balances,
+            );
+            if discriminator == DEPOSIT_SOL_TO_PAIR_DISCRIMINATOR {
+                order_data.instruction_type = "DepositSolToPair".to_string();
+                order_data.category = "deposit".to_string();
+                order_data.amount = -1.0 * vault_change;
+            } else {
+                order_data.instruction_type = "WithdrawSolFromPair".to_string();
+                order_data.category = "withdraw".to_string();
+                order_data.amount = vault_change;
+            }
+            order_data.side = "buy".to_string();
+            order_data.quantity = args.amountOfOrders as u64;
+        }
+        DEPOSIT_NFT_TO_PAIR_DISCRIMINATOR | WITHDRAW_NFT_FROM_PAIR_DISCRIMINATOR => {
+            if discriminator == DEPOSIT_NFT_TO_PAIR_DISCRIMINATOR {
+                order_data.instruction_type = "DepositNftToPair".to_string();
+                order_data.category = "deposit".to_string();
+            } else {
+                order_data.instruction_type = "WithdrawNftFromPair".to_string();
+                order_data.category = "withdraw".to_string();
+            }
+            order_data.side = "sell".to_string();
+            order_data.mint =
+                get_moved_mint(&order_data.maker, pre_token_balances, post_token_balances);
+            order_data.quantity = 1;
+        }
+        _ => return None,
+    }
+
+    Some(order_data)
+}
@@ -0,0 +1,91 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::pb::sf::solana::nft::orders::v1::OrderData;
+use crate::utils::{get_discriminator, WSOL_MINT};
+
+const CREATE_POOL_DISCRIMINATOR: u64 = 13564957318303552233;
+const SOL_DEPOSIT_BUY_DISCRIMINATOR: u64 = 17173813543012394306;
+const SOL_WITHDRAW_BUY_DISCRIMINATOR: u64 = 2591312163378309530;
+const DEPOSIT_SELL_DISCRIMINATOR: u64 = 3153938957887898512;
+const WITHDRAW_SELL_DISCRIMINATOR: u64 = 13402329834778798639;
+
+// Leading fields of CreatePoolArgs, the referral, cosigner and allowlist data that
+// follows is not needed here.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct CreatePoolLayout {
+    spotPrice: u64,
+    curveType: u8,
+    curveDelta: u64,
+    reinvestFulfillBuy: bool,
+    reinvestFulfillSell: bool,
+    expiry: i64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct SolDepositWithdrawBuyLayout {
+    paymentAmount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Default)]
+struct DepositWithdrawSellLayout {
+    assetAmount: u64,
+    allowlistAux: Option<String>,
+}
+
+pub fn parse_order_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+) -> Option<OrderData> {
+    let (discriminator, rest) = get_discriminator(&bytes_stream);
+
+    let mut order_data = OrderData::default();
+    order_data.platform = "coralcube_me_amm".to_string();
+    order_data.currency_mint = WSOL_MINT.to_string();
+    order_data.maker = input_accounts.get(0)?.to_string();
+    order_data.pool = input_accounts.get(2)?.to_string();
+
+    match discriminator {
+        CREATE_POOL_DISCRIMINATOR => {
+            let args = CreatePoolLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "CreatePool".to_string();
+            order_data.category = "create_pool".to_string();
+            order_data.price = args.spotPrice as f64;
+            order_data.expiry = args.expiry;
+        }
+        SOL_DEPOSIT_BUY_DISCRIMINATOR => {
+            let args = SolDepositWithdrawBuyLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "SolDepositBuy".to_string();
+            order_data.category = "deposit".to_string();
+            order_data.side = "buy".to_string();
+            order_data.amount = args.paymentAmount as f64;
+        }
+        SOL_WITHDRAW_BUY_DISCRIMINATOR => {
+            let args = SolDepositWithdrawBuyLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "SolWithdrawBuy".to_string();
+            order_data.category = "withdraw".to_string();
+            order_data.side = "buy".to_string();
+            order_data.amount = args.paymentAmount as f64;
+        }
+        DEPOSIT_SELL_DISCRIMINATOR => {
+            let args = DepositWithdrawSellLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "DepositSell".to_string();
+            order_data.category = "deposit".to_string();
+            order_data.side = "sell".to_string();
+            // owner, cosigner, pool, asset_metadata, asset_master_edition, asset_mint
+            order_data.mint = input_accounts.get(5)?.to_string();
+            order_data.quantity = args.assetAmount;
+        }
+        WITHDRAW_SELL_DISCRIMINATOR => {
+            let args = DepositWithdrawSellLayout::deserialize(&mut &rest[..]).ok()?;
+            order_data.instruction_type = "WithdrawSell".to_string();
+            order_data.category = "withdraw".to_string();
+            order_data.side = "sell".to_string();
+            // owner, cosigner, pool, asset_mint, the withdrawal takes no metadata accounts
+            order_data.mint = input_accounts.get(3)?.to_string();
+            order_data.quantity = args.assetAmount;
+        }
+        _ => return None,
+    }
+
+    Some(order_data)
+}
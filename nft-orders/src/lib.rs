@@ -0,0 +1,192 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(non_snake_case)]
+
+mod dapps;
+mod pb;
+mod utils;
+
+use pb::sf::solana::block_meta::v1::CollectionAttribution;
+use pb::sf::solana::nft::orders::v1::{OrderData, Output};
+use substreams::store::{StoreGet, StoreGetProto};
+use substreams_solana::pb::sf::solana::r#type::v1::{Block, TokenBalance};
+use utils::convert_to_date;
+
+#[substreams::handlers::map]
+fn map_block(
+    block: Block,
+    collections_store: StoreGetProto<CollectionAttribution>,
+) -> Result<Output, substreams::errors::Error> {
+    let slot = block.slot;
+    let timestamp = block.block_time.as_ref().unwrap().timestamp;
+
+    let mut data: Vec<OrderData> = vec![];
+
+    for trx in block.transactions_owned() {
+        let accounts = trx.resolved_accounts_as_strings();
+        if let Some(transaction) = trx.transaction {
+            let meta = trx.meta.unwrap();
+            if meta.err.is_some() {
+                continue;
+            }
+            let pre_balances = meta.pre_balances;
+            let post_balances = meta.post_balances;
+            let pre_token_balances = meta.pre_token_balances;
+            let post_token_balances = meta.post_token_balances;
+
+            let msg = transaction.message.unwrap();
+
+            for (idx, inst) in msg.instructions.into_iter().enumerate() {
+                let program = &accounts[inst.program_id_index as usize];
+
+                let order_data = get_order_data(
+                    program,
+                    inst.data,
+                    &inst.accounts,
+                    &accounts,
+                    &pre_balances,
+                    &post_balances,
+                    &pre_token_balances,
+                    &post_token_balances,
+                );
+                if order_data.is_some() {
+                    let mut od = order_data.unwrap();
+
+                    od.block_date = convert_to_date(timestamp);
+                    od.block_time = timestamp;
+                    od.block_slot = slot;
+                    od.tx_id = bs58::encode(&transaction.signatures[0]).into_string();
+                    od.txn_fee = meta.fee;
+                    od.instruction_index = idx as u32;
+                    od.outer_program = program.to_string();
+                    od.inner_program = "".to_string();
+                    od.inner_instruction_index = 0;
+                    od.is_inner_instruction = false;
+
+                    data.push(od);
+                }
+
+                meta.inner_instructions
+                    .iter()
+                    .filter(|inner_instruction| inner_instruction.index == idx as u32)
+                    .for_each(|inner_instruction| {
+                        inner_instruction.instructions.iter().enumerate().for_each(
+                            |(inner_idx, inner_inst)| {
+                                let inner_program = &accounts[inner_inst.program_id_index as usize];
+                                let order_data = get_order_data(
+                                    inner_program,
+                                    inner_inst.data.clone(),
+                                    &inner_inst.accounts,
+                                    &accounts,
+                                    &pre_balances,
+                                    &post_balances,
+                                    &pre_token_balances,
+                                    &post_token_balances,
+                                );
+                                if order_data.is_some() {
+                                    let mut od = order_data.unwrap();
+
+                                    od.block_date = convert_to_date(timestamp);
+                                    od.block_time = timestamp;
+                                    od.block_slot = slot;
+                                    od.tx_id =
+                                        bs58::encode(&transaction.signatures[0]).into_string();
+                                    od.txn_fee = meta.fee;
+                                    od.instruction_index = idx as u32;
+                                    od.outer_program = program.to_string();
+                                    od.inner_program = inner_program.to_string();
+                                    od.inner_instruction_index = inner_idx as u32;
+                                    od.is_inner_instruction = true;
+
+                                    data.push(od);
+                                }
+                            },
+                        )
+                    });
+            }
+        }
+    }
+
+    data.iter_mut()
+        .for_each(|od| apply_collection(od, &collections_store));
+
+    Ok(Output { data })
+}
+
+// Pool bids carry no mint and stay without a collection.
+fn apply_collection(od: &mut OrderData, collections_store: &StoreGetProto<CollectionAttribution>) {
+    if od.mint.is_empty() {
+        return;
+    }
+    if let Some(collection) = collections_store.get_last(&od.mint) {
+        od.collection_mint = collection.collection_mint;
+        od.collection_verified = collection.verified;
+    }
+}
+
+fn get_order_data(
+    dapp_address: &String,
+    instruction_data: Vec<u8>,
+    account_indices: &Vec<u8>,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
+    pre_token_balances: &Vec<TokenBalance>,
+    post_token_balances: &Vec<TokenBalance>,
+) -> Option<OrderData> {
+    if instruction_data.len() < 8 {
+        return None;
+    }
+    let input_accounts = prepare_input_accounts(account_indices, accounts);
+
+    let mut result = None;
+    match dapp_address.as_str() {
+        "TSWAPaqyCSx2KABk68Shruf4rp7CxcNi8hAsbdwmHbN" => {
+            result =
+                dapps::dapp_TSWAPaqyCSx2KABk68Shruf4rp7CxcNi8hAsbdwmHbN::parse_order_instruction(
+                    instruction_data,
+                    input_accounts,
+                    accounts,
+                    pre_balances,
+                    post_balances,
+                );
+        }
+        "M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K" => {
+            result =
+                dapps::dapp_M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K::parse_order_instruction(
+                    instruction_data,
+                    input_accounts,
+                );
+        }
+        "mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc" => {
+            result =
+                dapps::dapp_mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc::parse_order_instruction(
+                    instruction_data,
+                    input_accounts,
+                );
+        }
+        "hadeK9DLv9eA7ya5KCTqSvSvRZeJC3JgD5a9Y3CNbvu" => {
+            result =
+                dapps::dapp_hadeK9DLv9eA7ya5KCTqSvSvRZeJC3JgD5a9Y3CNbvu::parse_order_instruction(
+                    instruction_data,
+                    input_accounts,
+                    accounts,
+                    pre_balances,
+                    post_balances,
+                    pre_token_balances,
+                    post_token_balances,
+                );
+        }
+        _ => {}
+    }
+
+    return result;
+}
+
+fn prepare_input_accounts(account_indices: &Vec<u8>, accounts: &Vec<String>) -> Vec<String> {
+    let mut instruction_accounts: Vec<String> = vec![];
+    for (index, &el) in account_indices.iter().enumerate() {
+        instruction_accounts.push(accounts.as_slice()[el as usize].to_string());
+    }
+    return instruction_accounts;
+}
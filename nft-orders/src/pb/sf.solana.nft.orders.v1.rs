@@ -0,0 +1,60 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Output {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<OrderData>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OrderData {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="2")]
+    pub block_time: i64,
+    #[prost(uint64, required, tag="3")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="4")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="5")]
+    pub txn_fee: u64,
+    #[prost(string, required, tag="6")]
+    pub platform: ::prost::alloc::string::String,
+    #[prost(string, required, tag="7")]
+    pub instruction_type: ::prost::alloc::string::String,
+    #[prost(string, required, tag="8")]
+    pub category: ::prost::alloc::string::String,
+    #[prost(string, required, tag="9")]
+    pub side: ::prost::alloc::string::String,
+    #[prost(string, required, tag="10")]
+    pub maker: ::prost::alloc::string::String,
+    #[prost(string, required, tag="11")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, required, tag="12")]
+    pub pool: ::prost::alloc::string::String,
+    #[prost(double, required, tag="13")]
+    pub price: f64,
+    #[prost(uint64, required, tag="14")]
+    pub quantity: u64,
+    #[prost(double, required, tag="15")]
+    pub amount: f64,
+    #[prost(int64, required, tag="16")]
+    pub expiry: i64,
+    #[prost(string, required, tag="17")]
+    pub currency_mint: ::prost::alloc::string::String,
+    #[prost(uint32, required, tag="18")]
+    pub instruction_index: u32,
+    #[prost(string, required, tag="19")]
+    pub outer_program: ::prost::alloc::string::String,
+    #[prost(string, required, tag="20")]
+    pub inner_program: ::prost::alloc::string::String,
+    #[prost(uint32, required, tag="21")]
+    pub inner_instruction_index: u32,
+    #[prost(bool, required, tag="22")]
+    pub is_inner_instruction: bool,
+    #[prost(string, required, tag="23")]
+    pub collection_mint: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="24")]
+    pub collection_verified: bool,
+}
+// @@protoc_insertion_point(module)
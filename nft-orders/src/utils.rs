@@ -0,0 +1,58 @@
+extern crate chrono;
+use chrono::prelude::*;
+use substreams_solana::pb::sf::solana::r#type::v1::TokenBalance;
+
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+pub fn convert_to_date(ts: i64) -> String {
+    let nt = NaiveDateTime::from_timestamp_opt(ts, 0);
+    let dt: DateTime<Utc> = DateTime::from_naive_utc_and_offset(nt.unwrap(), Utc);
+    let res = dt.format("%Y-%m-%d");
+    return res.to_string();
+}
+
+pub fn get_discriminator(bytes_stream: &Vec<u8>) -> (u64, &[u8]) {
+    let (disc_bytes, rest) = bytes_stream.split_at(8);
+    let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
+    return (u64::from_le_bytes(disc_bytes_arr), rest);
+}
+
+// Lamports that left the address over the transaction, negative when it received them.
+pub fn get_sol_balance_change(
+    address: &String,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
+) -> f64 {
+    match accounts.iter().position(|r| r == address) {
+        Some(index) => pre_balances[index] as f64 - post_balances[index] as f64,
+        None => 0.0,
+    }
+}
+
+// First mint whose balance held by the owner changed, used when the instruction
+// does not pass the mint account itself.
+pub fn get_moved_mint(
+    owner: &String,
+    pre_token_balances: &Vec<TokenBalance>,
+    post_token_balances: &Vec<TokenBalance>,
+) -> String {
+    let get_amount = |balances: &Vec<TokenBalance>, mint: &String| {
+        balances
+            .iter()
+            .filter(|balance| &balance.owner == owner && &balance.mint == mint)
+            .filter_map(|balance| balance.ui_token_amount.as_ref())
+            .map(|amount| amount.amount.to_string())
+            .next()
+            .unwrap_or_default()
+    };
+
+    pre_token_balances
+        .iter()
+        .chain(post_token_balances.iter())
+        .filter(|balance| &balance.owner == owner && balance.mint != WSOL_MINT)
+        .map(|balance| &balance.mint)
+        .find(|mint| get_amount(pre_token_balances, mint) != get_amount(post_token_balances, mint))
+        .map(|mint| mint.to_string())
+        .unwrap_or_default()
+}
@@ -1,41 +1,39 @@
 use std::str::FromStr;
 
-use crate::pb::sf::solana::nft::trades::v1::TradeData;
+use substreams_solana::pb::sf::solana::r#type::v1::InnerInstruction;
 
-const BUY_DISCRIMINATOR: u8 = 5;
-
-pub fn parse_logs(log_messages: &Vec<String>, amount: f64, amm_fee: f64) -> Option<(f64, f64)> {
-    let mut result: Option<(f64, f64)> = None;
+use crate::{
+    pb::sf::solana::nft::trades::v1::TradeData,
+    utils::{get_creators_royalty, get_remaining_accounts, get_sol_balance_change, WSOL_MINT},
+};
 
-    for log_message in log_messages {
-        if log_message.starts_with("Program log: ")
-            & (amm_fee > 0.0)
-            & log_message.contains(&amm_fee.to_string())
-        {
-            let val_str = log_message
-                .replace("Program log: ", "")
-                .replacen(&amm_fee.to_string(), "", 1)
-                .replace("  ", " ")
-                .trim()
-                .to_string();
-            let string_items: Vec<&str> = val_str.split(" ").collect();
-
-            let amount: f64 = f64::from_str(string_items.get(0).unwrap()).unwrap();
-            let royalty: f64 = f64::from_str(string_items.get(1).unwrap()).unwrap();
-
-            result = Some((amount, royalty));
-        }
-    }
+const BUY_DISCRIMINATOR: u8 = 5;
 
-    return result;
+// The program logs "<price> <royalty> <fee>", only used to cross-check the transfers.
+pub fn parse_logs(log_messages: &Vec<String>, amm_fee: f64) -> Option<(f64, f64)> {
+    log_messages
+        .iter()
+        .filter(|log_message| {
+            log_message.starts_with("Program log: ")
+                & (amm_fee > 0.0)
+                & log_message.contains(&amm_fee.to_string())
+        })
+        .filter_map(|log_message| {
+            let val_str = log_message.replace("Program log: ", "");
+            let val_str = val_str.replacen(&amm_fee.to_string(), "", 1);
+            let mut string_items = val_str.split_whitespace();
+
+            let amount = f64::from_str(string_items.next()?).ok()?;
+            let royalty = f64::from_str(string_items.next()?).ok()?;
+            Some((amount, royalty))
+        })
+        .last()
 }
 
 pub fn enrich_with_logs_data(trade_data: &mut TradeData, log_messages: &Vec<String>) -> () {
-    let log_data = parse_logs(log_messages, trade_data.amount, trade_data.amm_fee);
+    let log_data = parse_logs(log_messages, trade_data.amm_fee);
     if log_data.is_some() {
-        let log_data_unwraped = log_data.unwrap();
-        trade_data.amount = log_data_unwraped.0;
-        trade_data.royalty = log_data_unwraped.1;
+        trade_data.log_royalty = log_data.unwrap().1;
     }
 }
 
@@ -58,10 +56,12 @@ pub fn parse_trade_instruction(
     pre_balances: &Vec<u64>,
     post_balances: &Vec<u64>,
     log_messages: &Vec<String>,
+    executed_instructions: &[InnerInstruction],
 ) -> Option<TradeData> {
-    let (disc_bytes, rest) = bytes_stream.split_at(1);
-    let disc_bytes_arr: [u8; 1] = disc_bytes.to_vec().try_into().unwrap();
-    let discriminator: u8 = u8::from_le_bytes(disc_bytes_arr);
+    if bytes_stream.is_empty() {
+        return None;
+    }
+    let discriminator: u8 = bytes_stream[0];
 
     let signer = accounts.get(0).unwrap().to_string();
 
@@ -78,7 +78,7 @@ pub fn parse_trade_instruction(
             trade_data.instruction_type = "Buy".to_string();
             trade_data.platform = "solanart".to_string();
 
-            trade_data.currency_mint = "So11111111111111111111111111111111111111112".to_string();
+            trade_data.currency_mint = WSOL_MINT.to_string();
             trade_data.taker_fee = 0.0;
             trade_data.maker_fee = 0.0;
             trade_data.amm_fee = get_sol_balance_change(
@@ -88,9 +88,15 @@ pub fn parse_trade_instruction(
                 post_balances,
             );
 
-            trade_data.mint = input_accounts.get(10).unwrap().to_string();
-            trade_data.buyer = input_accounts.get(0).unwrap().to_string();
-            trade_data.seller = input_accounts.get(3).unwrap().to_string();
+            let get_account = |index: usize| {
+                input_accounts
+                    .get(index)
+                    .map(|account| account.to_string())
+                    .unwrap_or_default()
+            };
+            trade_data.mint = get_account(10);
+            trade_data.buyer = get_account(0);
+            trade_data.seller = get_account(3);
 
             if signer.eq(&trade_data.buyer.to_string()) {
                 trade_data.category = "buy".to_string();
@@ -98,8 +104,25 @@ pub fn parse_trade_instruction(
                 trade_data.category = "sell".to_string();
             }
 
+            if trade_data.mint.is_empty() || trade_data.seller.is_empty() {
+                trade_data.parse_failed = true;
+                trade_data.parse_error = "missing instruction accounts".to_string();
+            }
+
+            // The buyer pays the full price, the seller receives it net of the marketplace fee
+            // and the royalty paid out to the creators.
+            trade_data.royalty = get_creators_royalty(
+                &get_remaining_accounts(&input_accounts),
+                &trade_data.seller,
+                &trade_data.currency_mint,
+                accounts,
+                executed_instructions,
+                &vec![],
+            );
             trade_data.amount =
-                get_sol_balance_change(&trade_data.seller, accounts, pre_balances, post_balances);
+                get_sol_balance_change(&trade_data.seller, accounts, pre_balances, post_balances)
+                    + trade_data.amm_fee
+                    + trade_data.royalty;
             enrich_with_logs_data(&mut trade_data, log_messages);
 
             result = Some(trade_data);
@@ -109,17 +132,3 @@ pub fn parse_trade_instruction(
 
     return result;
 }
-
-fn get_sol_balance_change(
-    address: &String,
-    accounts: &Vec<String>,
-    pre_balances: &Vec<u64>,
-    post_balances: &Vec<u64>,
-) -> f64 {
-    if let Some(index) = accounts.iter().position(|r| r == address) {
-        let pre_balance = pre_balances[index];
-        let post_balance = post_balances[index];
-        return post_balance as f64 - pre_balance as f64;
-    }
-    return 0.0;
-}
@@ -0,0 +1,44 @@
+use crate::pb::sf::solana::nft::trades::v1::TradeData;
+use crate::utils::set_transfer_payment;
+
+const CORE_PROGRAM_ADDRESS: &str = "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7";
+
+const TRANSFER_V1_DISCRIMINATOR: u8 = 14;
+
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
+    txn_fee: u64,
+) -> Option<TradeData> {
+    match *bytes_stream.first()? {
+        TRANSFER_V1_DISCRIMINATOR => {
+            let mut trade_data = TradeData::default();
+            trade_data.instruction_type = "TransferV1".to_string();
+            trade_data.platform = "metaplex_core".to_string();
+            trade_data.token_standard = "core".to_string();
+
+            // Unset optional accounts are passed as the program id, the owner signs
+            // as authority unless it also paid for the transfer.
+            trade_data.mint = input_accounts.get(0)?.to_string();
+            trade_data.buyer = input_accounts.get(4)?.to_string();
+            trade_data.seller = match input_accounts.get(3) {
+                Some(authority) if authority != CORE_PROGRAM_ADDRESS => authority.to_string(),
+                _ => input_accounts.get(2)?.to_string(),
+            };
+
+            set_transfer_payment(
+                &mut trade_data,
+                accounts,
+                pre_balances,
+                post_balances,
+                txn_fee,
+            )?;
+
+            Some(trade_data)
+        }
+        _ => None,
+    }
+}
@@ -2,9 +2,12 @@ use std::collections::HashSet;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde_json::Value;
-use substreams_solana::pb::sf::solana::r#type::v1::TokenBalance;
+use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstruction, TokenBalance};
 
-use crate::pb::sf::solana::nft::trades::v1::TradeData;
+use crate::{
+    pb::sf::solana::nft::trades::v1::TradeData,
+    utils::{get_creators_royalty, get_remaining_accounts},
+};
 
 const EXECUTE_SALE_V2_DISCRIMINATOR: u64 = 13922176540003654747;
 const OCP_EXECUTE_SALE_V2_DISCRIMINATOR: u64 = 6995388316419838920;
@@ -50,27 +53,25 @@ pub struct CoreExecuteSaleV2Layout {
     compressionProof: Option<Vec<u8>>,
 }
 
+// Royalty as logged by the program, only used to cross-check the transfers to creators.
 pub fn parse_logs(log_messages: &Vec<String>) -> Option<f64> {
-    let mut result: Option<f64> = None;
-
-    for log_message in log_messages {
-        if log_message.starts_with("Program log: ") & log_message.contains("royalty") {
+    log_messages
+        .iter()
+        .filter(|log_message| {
+            log_message.starts_with("Program log: ") & log_message.contains("royalty")
+        })
+        .filter_map(|log_message| {
             let json_str = log_message.replace("Program log: ", "").trim().to_string();
-            let mut json_obj: Value = serde_json::from_str(&json_str).unwrap();
-            let royalty_value = json_obj.get_mut("royalty").unwrap();
-            let royalty_numeric: f64 = royalty_value.to_string().parse().unwrap();
-            result = Some(royalty_numeric);
-        }
-    }
-
-    return result;
+            let json_obj: Value = serde_json::from_str(&json_str).ok()?;
+            json_obj.get("royalty")?.as_f64()
+        })
+        .last()
 }
 
 pub fn enrich_with_logs_data(trade_data: &mut TradeData, log_messages: &Vec<String>) -> () {
     let log_data = parse_logs(log_messages);
     if log_data.is_some() {
-        let log_data_unwraped = log_data.unwrap();
-        trade_data.royalty = log_data_unwraped;
+        trade_data.log_royalty = log_data.unwrap();
     }
 }
 
@@ -98,7 +99,7 @@ pub fn get_currency_mint(post_token_balances: &Vec<TokenBalance>, nft_mint: &Str
             return result.get(0).unwrap().to_string();
         }
         _ => {
-            panic!("Found 3 distinct mints");
+            return "".to_string();
         }
     }
 }
@@ -127,7 +128,7 @@ pub fn get_currency_mint_core(post_token_balances: &Vec<TokenBalance>, nft_mint:
             return result.get(0).unwrap().to_string();
         }
         _ => {
-            panic!("Found more than 1 distinct mints");
+            return "".to_string();
         }
     }
 }
@@ -136,204 +137,140 @@ pub fn parse_trade_instruction(
     bytes_stream: Vec<u8>,
     input_accounts: Vec<String>,
     accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
     log_messages: &Vec<String>,
+    pre_token_balances: &Vec<TokenBalance>,
     post_token_balances: &Vec<TokenBalance>,
+    executed_instructions: &[InnerInstruction],
 ) -> Option<TradeData> {
+    if bytes_stream.len() < 8 {
+        return None;
+    }
     let (disc_bytes, rest) = bytes_stream.split_at(8);
     let disc_bytes_arr: [u8; 8] = disc_bytes.to_vec().try_into().unwrap();
     let discriminator: u64 = u64::from_le_bytes(disc_bytes_arr);
 
     let signer = accounts.get(0).unwrap().to_string();
 
-    let mut result = None;
-    let mut trade_data: TradeData;
+    let mut trade_data = TradeData::default();
+    let sale: Option<(u64, i16, u16)>;
 
     match discriminator {
         EXECUTE_SALE_V2_DISCRIMINATOR => {
-            trade_data = TradeData::default();
             trade_data.instruction_type = "ExecuteSaleV2".to_string();
-            trade_data.platform = "magiceden".to_string();
-
-            trade_data.mint = input_accounts.get(4).unwrap().to_string();
-            trade_data.buyer = input_accounts.get(0).unwrap().to_string();
-            trade_data.seller = input_accounts.get(1).unwrap().to_string();
-
-            if signer.eq(&trade_data.buyer.to_string()) {
-                trade_data.category = "buy".to_string();
-            } else {
-                trade_data.category = "sell".to_string();
-            }
-
+            set_parties(&mut trade_data, &input_accounts, 4, 0, 1);
             trade_data.currency_mint = get_currency_mint(post_token_balances, &trade_data.mint);
 
-            let instruction_data: ExecuteSaleV2Layout;
-            if rest.len() > 38 {
-                let (rest_split, _) = rest.split_at(38);
-                instruction_data = ExecuteSaleV2Layout::try_from_slice(rest_split).unwrap();
-            } else {
-                instruction_data = ExecuteSaleV2Layout::try_from_slice(rest).unwrap();
-            }
-
-            trade_data.taker_fee =
-                ((instruction_data.takerFeeBp as u64 * instruction_data.price) / 10000) as f64;
-            trade_data.maker_fee = ((instruction_data.makerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.amount = instruction_data.price as f64
-                + trade_data.taker_fee as f64
-                + trade_data.maker_fee as f64;
-            trade_data.amm_fee = 0.0;
-            enrich_with_logs_data(&mut trade_data, log_messages);
-
-            result = Some(trade_data);
+            sale = ExecuteSaleV2Layout::deserialize(&mut rest.clone())
+                .ok()
+                .map(|data| (data.price, data.makerFeeBp, data.takerFeeBp));
         }
         OCP_EXECUTE_SALE_V2_DISCRIMINATOR => {
-            trade_data = TradeData::default();
             trade_data.instruction_type = "OcpExecuteSaleV2".to_string();
-            trade_data.platform = "magiceden".to_string();
-
-            trade_data.mint = input_accounts.get(7).unwrap().to_string();
-            trade_data.buyer = input_accounts.get(1).unwrap().to_string();
-            trade_data.seller = input_accounts.get(2).unwrap().to_string();
-
-            if signer.eq(&trade_data.buyer.to_string()) {
-                trade_data.category = "buy".to_string();
-            } else {
-                trade_data.category = "sell".to_string();
-            }
-
+            set_parties(&mut trade_data, &input_accounts, 7, 1, 2);
             trade_data.currency_mint = get_currency_mint(post_token_balances, &trade_data.mint);
 
-            let instruction_data = OCPExecuteSaleV2Layout::try_from_slice(rest).unwrap();
-            trade_data.taker_fee = ((instruction_data.takerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.maker_fee = ((instruction_data.makerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.amount = instruction_data.price as f64
-                + trade_data.maker_fee as f64
-                + trade_data.taker_fee as f64;
-            trade_data.amm_fee = 0.0;
-
-            enrich_with_logs_data(&mut trade_data, log_messages);
-
-            result = Some(trade_data);
+            sale = OCPExecuteSaleV2Layout::deserialize(&mut rest.clone())
+                .ok()
+                .map(|data| (data.price, data.makerFeeBp, data.takerFeeBp));
         }
         MIP1_EXECUTE_SALE_V2_DISCRIMINATOR => {
-            trade_data = TradeData::default();
             trade_data.instruction_type = "Mip1ExecuteSaleV2".to_string();
-            trade_data.platform = "magiceden".to_string();
-
-            trade_data.mint = input_accounts.get(7).unwrap().to_string();
-            trade_data.buyer = input_accounts.get(1).unwrap().to_string();
-            trade_data.seller = input_accounts.get(2).unwrap().to_string();
-
-            if signer.eq(&trade_data.buyer.to_string()) {
-                trade_data.category = "buy".to_string();
-            } else {
-                trade_data.category = "sell".to_string();
-            }
-
+            set_parties(&mut trade_data, &input_accounts, 7, 1, 2);
             trade_data.currency_mint = get_currency_mint(post_token_balances, &trade_data.mint);
 
-            let instruction_data: MIP1ExecuteSaleV2Layout;
-            if rest.len() > 12 {
-                let (rest_split, _) = rest.split_at(12);
-                instruction_data = MIP1ExecuteSaleV2Layout::try_from_slice(rest_split).unwrap();
-            } else {
-                instruction_data = MIP1ExecuteSaleV2Layout::try_from_slice(rest).unwrap();
-            }
-
-            trade_data.taker_fee = ((instruction_data.takerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.maker_fee = ((instruction_data.makerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.amount = instruction_data.price as f64
-                + trade_data.taker_fee as f64
-                + trade_data.maker_fee as f64;
-            trade_data.amm_fee = 0.0;
-            enrich_with_logs_data(&mut trade_data, log_messages);
-
-            result = Some(trade_data);
+            sale = MIP1ExecuteSaleV2Layout::deserialize(&mut rest.clone())
+                .ok()
+                .map(|data| (data.price, data.makerFeeBp, data.takerFeeBp));
         }
         EXT_EXECUTE_SALE_V2_DISCRIMINATOR => {
-            trade_data = TradeData::default();
             trade_data.instruction_type = "ExtExecuteSaleV2".to_string();
-            trade_data.platform = "magiceden".to_string();
-
-            trade_data.mint = input_accounts.get(7).unwrap().to_string();
-            trade_data.buyer = input_accounts.get(1).unwrap().to_string();
-            trade_data.seller = input_accounts.get(2).unwrap().to_string();
-
-            if signer.eq(&trade_data.buyer.to_string()) {
-                trade_data.category = "buy".to_string();
-            } else {
-                trade_data.category = "sell".to_string();
-            }
-
+            set_parties(&mut trade_data, &input_accounts, 7, 1, 2);
             trade_data.currency_mint = get_currency_mint(post_token_balances, &trade_data.mint);
 
-            let instruction_data: ExtExecuteSaleV2Layout;
-            if rest.len() > 12 {
-                let (rest_split, _) = rest.split_at(12);
-                instruction_data = ExtExecuteSaleV2Layout::try_from_slice(rest_split).unwrap();
-            } else {
-                instruction_data = ExtExecuteSaleV2Layout::try_from_slice(rest).unwrap();
-            }
-
-            trade_data.taker_fee = ((instruction_data.takerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.maker_fee = ((instruction_data.makerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.amount = instruction_data.price as f64
-                + trade_data.taker_fee as f64
-                + trade_data.maker_fee as f64;
-            trade_data.amm_fee = 0.0;
-            enrich_with_logs_data(&mut trade_data, log_messages);
-
-            result = Some(trade_data);
+            sale = ExtExecuteSaleV2Layout::deserialize(&mut rest.clone())
+                .ok()
+                .map(|data| (data.price, data.makerFeeBp, data.takerFeeBp));
         }
         CORE_EXECUTE_SALE_V2_DISCRIMINATOR => {
-            trade_data = TradeData::default();
             trade_data.instruction_type = "CoreExecuteSaleV2".to_string();
-            trade_data.platform = "magiceden".to_string();
+            trade_data.token_standard = "core".to_string();
+            set_parties(&mut trade_data, &input_accounts, 5, 1, 2);
+            trade_data.currency_mint =
+                get_currency_mint_core(post_token_balances, &trade_data.mint);
+
+            sale = CoreExecuteSaleV2Layout::deserialize(&mut rest.clone())
+                .ok()
+                .map(|data| (data.price, data.makerFeeBp, data.takerFeeBp));
+        }
+        _ => return None,
+    }
 
-            trade_data.mint = input_accounts.get(5).unwrap().to_string();
-            trade_data.buyer = input_accounts.get(1).unwrap().to_string();
-            trade_data.seller = input_accounts.get(2).unwrap().to_string();
+    trade_data.platform = "magiceden".to_string();
+    if signer.eq(&trade_data.buyer.to_string()) {
+        trade_data.category = "buy".to_string();
+    } else {
+        trade_data.category = "sell".to_string();
+    }
 
-            if signer.eq(&trade_data.buyer.to_string()) {
-                trade_data.category = "buy".to_string();
-            } else {
-                trade_data.category = "sell".to_string();
-            }
+    match sale {
+        Some((price, maker_fee_bp, taker_fee_bp)) => {
+            trade_data.taker_fee = (taker_fee_bp as f64 * price as f64) / 10000.0;
+            trade_data.maker_fee = (maker_fee_bp as f64 * price as f64) / 10000.0;
+            trade_data.amount = price as f64 + trade_data.taker_fee + trade_data.maker_fee;
+        }
+        None => flag(&mut trade_data, "failed to decode instruction args"),
+    }
+    trade_data.amm_fee = 0.0;
 
-            trade_data.currency_mint = get_currency_mint_core(post_token_balances, &trade_data.mint);
+    if trade_data.mint.is_empty() || trade_data.buyer.is_empty() || trade_data.seller.is_empty() {
+        flag(&mut trade_data, "missing instruction accounts");
+    }
+    if trade_data.currency_mint.is_empty() {
+        flag(&mut trade_data, "ambiguous currency mint");
+    } else {
+        trade_data.royalty = get_creators_royalty(
+            &get_remaining_accounts(&input_accounts),
+            &trade_data.seller,
+            &trade_data.currency_mint,
+            accounts,
+            executed_instructions,
+            post_token_balances,
+        );
+    }
+    // Core royalties are only enforced by the asset's plugin, a payout to the creators
+    // is the only trace of it.
+    if trade_data.token_standard == "core" {
+        trade_data.royalty_enforced = trade_data.royalty > 0.0;
+    }
+    enrich_with_logs_data(&mut trade_data, log_messages);
 
-            let instruction_data: CoreExecuteSaleV2Layout;
-            instruction_data = CoreExecuteSaleV2Layout::deserialize(&mut rest.clone()).unwrap();
+    Some(trade_data)
+}
 
-            trade_data.taker_fee = ((instruction_data.takerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.maker_fee = ((instruction_data.makerFeeBp as f64
-                * instruction_data.price as f64)
-                / 10000.0) as f64;
-            trade_data.amount = instruction_data.price as f64
-                + trade_data.taker_fee as f64
-                + trade_data.maker_fee as f64;
-            trade_data.amm_fee = 0.0;
-            enrich_with_logs_data(&mut trade_data, log_messages);
+fn set_parties(
+    trade_data: &mut TradeData,
+    input_accounts: &Vec<String>,
+    mint_index: usize,
+    buyer_index: usize,
+    seller_index: usize,
+) {
+    let get_account = |index: usize| {
+        input_accounts
+            .get(index)
+            .map(|account| account.to_string())
+            .unwrap_or_default()
+    };
+    trade_data.mint = get_account(mint_index);
+    trade_data.buyer = get_account(buyer_index);
+    trade_data.seller = get_account(seller_index);
+}
 
-            result = Some(trade_data);
-        }
-        _ => {}
+fn flag(trade_data: &mut TradeData, reason: &str) {
+    trade_data.parse_failed = true;
+    if !trade_data.parse_error.is_empty() {
+        trade_data.parse_error.push_str("; ");
     }
-
-    return result;
+    trade_data.parse_error.push_str(reason);
 }
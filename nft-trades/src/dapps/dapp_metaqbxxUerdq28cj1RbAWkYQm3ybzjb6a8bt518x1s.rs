@@ -0,0 +1,48 @@
+use crate::pb::sf::solana::nft::trades::v1::TradeData;
+use crate::utils::set_transfer_payment;
+
+const TOKEN_METADATA_PROGRAM_ADDRESS: &str = "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s";
+
+const TRANSFER_DISCRIMINATOR: u8 = 49;
+
+pub fn parse_trade_instruction(
+    bytes_stream: Vec<u8>,
+    input_accounts: Vec<String>,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
+    txn_fee: u64,
+) -> Option<TradeData> {
+    match *bytes_stream.first()? {
+        TRANSFER_DISCRIMINATOR => {
+            let mut trade_data = TradeData::default();
+            trade_data.instruction_type = "Transfer".to_string();
+            trade_data.platform = "metaplex_token_metadata".to_string();
+
+            trade_data.seller = input_accounts.get(1)?.to_string();
+            trade_data.buyer = input_accounts.get(3)?.to_string();
+            trade_data.mint = input_accounts.get(4)?.to_string();
+
+            // Unset optional accounts are passed as the program id. Only programmable NFTs
+            // carry token records, their royalties are enforced when a rule set is attached.
+            let is_set = |index: usize| {
+                input_accounts
+                    .get(index)
+                    .map_or(false, |account| account != TOKEN_METADATA_PROGRAM_ADDRESS)
+            };
+            trade_data.token_standard = if is_set(7) { "pnft" } else { "nft" }.to_string();
+            trade_data.royalty_enforced = is_set(7) && is_set(16);
+
+            set_transfer_payment(
+                &mut trade_data,
+                accounts,
+                pre_balances,
+                post_balances,
+                txn_fee,
+            )?;
+
+            Some(trade_data)
+        }
+        _ => None,
+    }
+}
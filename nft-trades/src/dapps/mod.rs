@@ -1,7 +1,9 @@
 pub mod dapp_CJsLwbP1iu5DuUikHEJnLfANgKy6stB2uFgvBBHoyxwz;
+pub mod dapp_CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7;
 pub mod dapp_HYPERfwdTjyJ2SCaKHmpF2MtrXqWxrsotYDsTrshHWq8;
 pub mod dapp_M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K;
 pub mod dapp_SNPRohhBurQwrpwAptw1QYtpFdfEKitr4WSJ125cN1g;
 pub mod dapp_TSWAPaqyCSx2KABk68Shruf4rp7CxcNi8hAsbdwmHbN;
 pub mod dapp_hadeK9DLv9eA7ya5KCTqSvSvRZeJC3JgD5a9Y3CNbvu;
+pub mod dapp_metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s;
 pub mod dapp_mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc;
@@ -6,13 +6,33 @@ mod dapps;
 mod pb;
 mod utils;
 
+use pb::sf::solana::block_meta::v1::CollectionAttribution;
 use pb::sf::solana::nft::trades::v1::{Output, TradeData};
 use substreams::log;
-use substreams_solana::pb::sf::solana::r#type::v1::{Block, TokenBalance};
+use substreams::store::{StoreGet, StoreGetProto};
+use substreams_solana::pb::sf::solana::r#type::v1::{Block, InnerInstruction, TokenBalance};
 use utils::convert_to_date;
 
+// Core and token metadata transfers made by these marketplaces are reported by their own dapp.
+const MARKETPLACE_PROGRAMS: [&str; 7] = [
+    "TSWAPaqyCSx2KABk68Shruf4rp7CxcNi8hAsbdwmHbN",
+    "M2mx93ekt1fmXSVkTrUL9xVFHkmME8HTUi5Cyc5aF7K",
+    "hadeK9DLv9eA7ya5KCTqSvSvRZeJC3JgD5a9Y3CNbvu",
+    "mmm3XBJg5gk8XJxEKBvdgptZz6SgK4tXvn36sodowMc",
+    "CJsLwbP1iu5DuUikHEJnLfANgKy6stB2uFgvBBHoyxwz",
+    "SNPRohhBurQwrpwAptw1QYtpFdfEKitr4WSJ125cN1g",
+    "HYPERfwdTjyJ2SCaKHmpF2MtrXqWxrsotYDsTrshHWq8",
+];
+const TRANSFER_PROGRAMS: [&str; 2] = [
+    "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7",
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+];
+
 #[substreams::handlers::map]
-fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
+fn map_block(
+    block: Block,
+    collections_store: StoreGetProto<CollectionAttribution>,
+) -> Result<Output, substreams::errors::Error> {
     let slot = block.slot;
     let parent_slot = block.parent_slot;
     let timestamp = block.block_time.as_ref().unwrap().timestamp;
@@ -36,6 +56,12 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
                 let program = &accounts[inst.program_id_index as usize];
                 log::info!("Checking program: {}", program);
 
+                let executed_instructions: Vec<InnerInstruction> = meta
+                    .inner_instructions
+                    .iter()
+                    .filter(|inner_instruction| inner_instruction.index == idx as u32)
+                    .flat_map(|inner_instruction| inner_instruction.instructions.iter().cloned())
+                    .collect();
                 let trade_data = get_trade_data(
                     program,
                     inst.data,
@@ -44,7 +70,10 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
                     &pre_balances,
                     &post_balances,
                     &meta.log_messages,
+                    &pre_token_balances,
                     &post_token_balances,
+                    meta.fee,
+                    &executed_instructions,
                 );
                 if trade_data.is_some() {
                     let mut td = trade_data.unwrap();
@@ -60,6 +89,7 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
                     td.inner_program = "".to_string();
                     td.inner_instruxtion_index = 0;
                     td.is_inner_instruction = false;
+                    td.success = meta.err.is_none();
 
                     data.push(td);
                 }
@@ -71,6 +101,11 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
                         inner_instruction.instructions.iter().enumerate().for_each(
                             |(inner_idx, inner_inst)| {
                                 let inner_program = &accounts[inner_inst.program_id_index as usize];
+                                if TRANSFER_PROGRAMS.contains(&inner_program.as_str())
+                                    && MARKETPLACE_PROGRAMS.contains(&program.as_str())
+                                {
+                                    return;
+                                }
                                 let trade_data = get_trade_data(
                                     inner_program,
                                     inner_inst.data.clone(),
@@ -79,7 +114,13 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
                                     &pre_balances,
                                     &post_balances,
                                     &meta.log_messages,
+                                    &pre_token_balances,
                                     &post_token_balances,
+                                    meta.fee,
+                                    get_executed_instructions(
+                                        &inner_instruction.instructions,
+                                        inner_idx,
+                                    ),
                                 );
                                 if trade_data.is_some() {
                                     let mut td = trade_data.unwrap();
@@ -95,6 +136,7 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
                                     td.inner_program = inner_program.to_string();
                                     td.inner_instruxtion_index = inner_idx as u32;
                                     td.is_inner_instruction = true;
+                                    td.success = meta.err.is_none();
 
                                     data.push(td);
                                 }
@@ -105,10 +147,19 @@ fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
         }
     }
 
+    data.iter_mut().for_each(|td| apply_collection(td, &collections_store));
+
     log::info!("Block {}: Processed {} transactions, found {} trades", slot, tx_count, data.len());
     Ok(Output { data })
 }
 
+fn apply_collection(td: &mut TradeData, collections_store: &StoreGetProto<CollectionAttribution>) {
+    if let Some(collection) = collections_store.get_last(&td.mint) {
+        td.collection_mint = collection.collection_mint;
+        td.collection_verified = collection.verified;
+    }
+}
+
 fn get_trade_data(
     dapp_address: &String,
     instruction_data: Vec<u8>,
@@ -117,7 +168,10 @@ fn get_trade_data(
     pre_balances: &Vec<u64>,
     post_balances: &Vec<u64>,
     log_messages: &Vec<String>,
+    pre_token_balances: &Vec<TokenBalance>,
     post_token_balances: &Vec<TokenBalance>,
+    txn_fee: u64,
+    executed_instructions: &[InnerInstruction],
 ) -> Option<TradeData> {
     let input_accounts = prepare_input_accounts(account_indices, accounts);
 
@@ -137,8 +191,12 @@ fn get_trade_data(
                     instruction_data,
                     input_accounts,
                     accounts,
+                    pre_balances,
+                    post_balances,
                     log_messages,
+                    pre_token_balances,
                     post_token_balances,
+                    executed_instructions,
                 );
         }
         "hadeK9DLv9eA7ya5KCTqSvSvRZeJC3JgD5a9Y3CNbvu" => {
@@ -171,6 +229,7 @@ fn get_trade_data(
                     pre_balances,
                     post_balances,
                     log_messages,
+                    executed_instructions,
                 );
         }
         "SNPRohhBurQwrpwAptw1QYtpFdfEKitr4WSJ125cN1g" => {
@@ -194,12 +253,49 @@ fn get_trade_data(
                     post_token_balances,
             );
         }
+        "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7" => {
+            result =
+                dapps::dapp_CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                    accounts,
+                    pre_balances,
+                    post_balances,
+                    txn_fee,
+                );
+        }
+        "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s" => {
+            result =
+                dapps::dapp_metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s::parse_trade_instruction(
+                    instruction_data,
+                    input_accounts,
+                    accounts,
+                    pre_balances,
+                    post_balances,
+                    txn_fee,
+                );
+        }
         _ => {}
     }
 
     return result;
 }
 
+// Instructions run by an inner instruction's CPI, up to the next call into the same
+// program.
+fn get_executed_instructions(
+    instructions: &[InnerInstruction],
+    inner_idx: usize,
+) -> &[InnerInstruction] {
+    let program_id_index = instructions[inner_idx].program_id_index;
+    let rest = &instructions[inner_idx + 1..];
+    let end = rest
+        .iter()
+        .position(|inner_inst| inner_inst.program_id_index == program_id_index)
+        .unwrap_or(rest.len());
+    &rest[..end]
+}
+
 fn prepare_input_accounts(account_indices: &Vec<u8>, accounts: &Vec<String>) -> Vec<String> {
     let mut instruction_accounts: Vec<String> = vec![];
     for (index, &el) in account_indices.iter().enumerate() {
@@ -1,6 +1,13 @@
 // @generated
 pub mod sf {
     pub mod solana {
+        pub mod block_meta {
+            // @@protoc_insertion_point(attribute:sf.solana.block_meta.v1)
+            pub mod v1 {
+                include!("../../../metaplex/token-metadata/src/pb/sf.solana.block_meta.v1.rs");
+                // @@protoc_insertion_point(sf.solana.block_meta.v1)
+            }
+        }
         pub mod nft {
             pub mod trades {
                 // @@protoc_insertion_point(attribute:sf.solana.nft.trades.v1)
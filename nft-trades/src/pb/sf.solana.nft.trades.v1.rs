@@ -52,5 +52,21 @@ pub struct TradeData {
     pub platform: ::prost::alloc::string::String,
     #[prost(string, required, tag="22")]
     pub currency_mint: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="23")]
+    pub parse_failed: bool,
+    #[prost(string, required, tag="24")]
+    pub parse_error: ::prost::alloc::string::String,
+    #[prost(double, required, tag="25")]
+    pub log_royalty: f64,
+    #[prost(string, required, tag="26")]
+    pub collection_mint: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="27")]
+    pub collection_verified: bool,
+    #[prost(string, required, tag="28")]
+    pub token_standard: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="29")]
+    pub royalty_enforced: bool,
+    #[prost(bool, required, tag="30")]
+    pub success: bool,
 }
 // @@protoc_insertion_point(module)
@@ -1,5 +1,17 @@
 extern crate chrono;
 use chrono::prelude::*;
+use substreams_solana::pb::sf::solana::r#type::v1::{InnerInstruction, TokenBalance};
+
+use crate::pb::sf::solana::nft::trades::v1::TradeData;
+
+pub const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+
+const SYSTEM_PROGRAM_ADDRESS: &str = "11111111111111111111111111111111";
+const TOKEN_PROGRAM_ADDRESS: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM_ADDRESS: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const SYSTEM_TRANSFER_INSTRUCTION: u32 = 2;
+const TOKEN_TRANSFER_INSTRUCTION: u8 = 3;
+const TOKEN_TRANSFER_CHECKED_INSTRUCTION: u8 = 12;
 
 pub fn convert_to_date(ts: i64) -> String {
     let nt = NaiveDateTime::from_timestamp_opt(ts, 0);
@@ -7,3 +19,126 @@ pub fn convert_to_date(ts: i64) -> String {
     let res = dt.format("%Y-%m-%d");
     return res.to_string();
 }
+
+// Programs and sysvars that close the fixed account list of marketplace instructions,
+// anything passed after the last of them is a remaining account (creators).
+const FIXED_ACCOUNT_PROGRAMS: [&str; 9] = [
+    "11111111111111111111111111111111",
+    "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+    "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb",
+    "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL",
+    "SysvarRent111111111111111111111111111111111",
+    "Sysvar1nstructions1111111111111111111111111",
+    "metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s",
+    "auth9SigNpDKz4sJJ1DfCTuZrZNSAgh9sFD3rboVmgg",
+    "CoREENxT6tW1HoK8ypY1SxRMZTcVPm7R94rH4PZNhX7",
+];
+
+pub fn get_remaining_accounts(input_accounts: &Vec<String>) -> Vec<String> {
+    match input_accounts
+        .iter()
+        .rposition(|account| FIXED_ACCOUNT_PROGRAMS.contains(&account.as_str()))
+    {
+        Some(index) => input_accounts[index + 1..].to_vec(),
+        None => vec![],
+    }
+}
+
+pub fn get_sol_balance_change(
+    address: &String,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
+) -> f64 {
+    match accounts.iter().position(|r| r == address) {
+        Some(index) => {
+            *post_balances.get(index).unwrap_or(&0) as f64
+                - *pre_balances.get(index).unwrap_or(&0) as f64
+        }
+        None => 0.0,
+    }
+}
+
+// Sum of the transfers the instruction itself made to the creators, in lamports or raw
+// token units. Balance changes would also count the other sales of a sweep and whatever
+// else a creator receives in the transaction, marketplace fees included. SPL currencies
+// are paid to the creators' token accounts, which the remaining accounts do not always
+// list. A creator selling their own item receives the proceeds, so the seller is never
+// counted.
+pub fn get_creators_royalty(
+    creators: &Vec<String>,
+    seller: &String,
+    currency_mint: &String,
+    accounts: &Vec<String>,
+    instructions: &[InnerInstruction],
+    post_token_balances: &Vec<TokenBalance>,
+) -> f64 {
+    let is_paid_creator = |address: &String| address != seller && creators.contains(address);
+
+    instructions
+        .iter()
+        .filter_map(|inst| {
+            let program = accounts.get(inst.program_id_index as usize)?;
+            let get_account =
+                |position: usize| accounts.get(*inst.accounts.get(position)? as usize);
+            match program.as_str() {
+                SYSTEM_PROGRAM_ADDRESS if currency_mint.eq(WSOL_MINT) => {
+                    let instruction = u32::from_le_bytes(inst.data.get(0..4)?.try_into().ok()?);
+                    if instruction != SYSTEM_TRANSFER_INSTRUCTION
+                        || !is_paid_creator(get_account(1)?)
+                    {
+                        return None;
+                    }
+                    Some(u64::from_le_bytes(inst.data.get(4..12)?.try_into().ok()?))
+                }
+                TOKEN_PROGRAM_ADDRESS | TOKEN_2022_PROGRAM_ADDRESS => {
+                    let destination = match *inst.data.first()? {
+                        TOKEN_TRANSFER_INSTRUCTION => 1,
+                        TOKEN_TRANSFER_CHECKED_INSTRUCTION => 2,
+                        _ => return None,
+                    };
+                    let destination_index = *inst.accounts.get(destination)? as u32;
+                    let destination = post_token_balances
+                        .iter()
+                        .find(|token_balance| token_balance.account_index == destination_index)?;
+                    if destination.mint != *currency_mint || !is_paid_creator(&destination.owner) {
+                        return None;
+                    }
+                    Some(u64::from_le_bytes(inst.data.get(1..9)?.try_into().ok()?))
+                }
+                _ => None,
+            }
+        })
+        .map(|amount| amount as f64)
+        .sum()
+}
+
+// Standalone asset transfers are sales when the receiver paid lamports and the previous
+// owner received some, the buyer's outflow is reported as the amount. Transfers without
+// a payment are not trades. The fee payer's change excludes the transaction fee.
+pub fn set_transfer_payment(
+    trade_data: &mut TradeData,
+    accounts: &Vec<String>,
+    pre_balances: &Vec<u64>,
+    post_balances: &Vec<u64>,
+    txn_fee: u64,
+) -> Option<()> {
+    let get_change = |address: &String| {
+        let change = get_sol_balance_change(address, accounts, pre_balances, post_balances);
+        if accounts.first() == Some(address) {
+            change + txn_fee as f64
+        } else {
+            change
+        }
+    };
+    let buyer_change = get_change(&trade_data.buyer);
+    let seller_change = get_change(&trade_data.seller);
+    if buyer_change >= 0.0 || seller_change <= 0.0 {
+        return None;
+    }
+
+    trade_data.currency_mint = WSOL_MINT.to_string();
+    trade_data.category = "buy".to_string();
+    trade_data.amount = -1.0 * buyer_change;
+    Some(())
+}
@@ -0,0 +1,326 @@
+mod pb;
+
+use pb::sf::solana::nft::trades::v1::{Output as NftOutput, TradeData};
+use pb::sf::solana::nft::wash::trading::v1::{
+    CollectionWashVolume, CollectionWashVolumes, Output, WashTradeData,
+};
+use substreams::store::{
+    StoreAdd, StoreAddFloat64, StoreGet, StoreGetFloat64, StoreGetInt64, StoreGetString, StoreNew,
+    StoreSet, StoreSetInt64, StoreSetString,
+};
+use substreams_solana::pb::sf::solana::r#type::v1::{Block, TokenBalance, TransactionStatusMeta};
+
+const SYSTEM_PROGRAM_ADDRESS: &str = "11111111111111111111111111111111";
+const SYSTEM_TRANSFER_INSTRUCTION: u32 = 2;
+const SYSTEM_TRANSFER_WITH_SEED_INSTRUCTION: u32 = 11;
+const TOKEN_PROGRAM_ADDRESS: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+const TOKEN_2022_PROGRAM_ADDRESS: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+const TOKEN_TRANSFER_INSTRUCTION: u8 = 3;
+const TOKEN_TRANSFER_CHECKED_INSTRUCTION: u8 = 12;
+const WSOL_MINT: &str = "So11111111111111111111111111111111111111112";
+const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+// How old the first leg of a round trip, or a transfer from the seller to the buyer,
+// can be and still count against a sale.
+const ROUND_TRIP_WINDOW_SECONDS: i64 = 7 * 86_400;
+const FUNDING_WINDOW_SECONDS: i64 = 7 * 86_400;
+
+#[substreams::handlers::store]
+fn store_trade_history(nft_trades: NftOutput, store: StoreSetString) {
+    for (ordinal, td) in get_successful_trades(&nft_trades) {
+        store.set(
+            ordinal,
+            format!("sold:{}:{}", td.mint, td.seller),
+            &format!("{}:{}", td.buyer, td.block_time),
+        );
+        store.set(
+            ordinal,
+            format!("trader:{}", td.buyer),
+            &td.block_time.to_string(),
+        );
+        store.set(
+            ordinal,
+            format!("trader:{}", td.seller),
+            &td.block_time.to_string(),
+        );
+    }
+}
+
+// Only transfers sent by wallets that traded NFTs before are kept, that is the only
+// side a funding check looks up. Transfers are written at the ordinal of the first sale
+// after them, so a sale reads the fundings made before its own transaction, and the
+// transactions holding a sale are skipped since they move the payment and the item.
+#[substreams::handlers::store]
+fn store_wallet_fundings(
+    block: Block,
+    nft_trades: NftOutput,
+    history_store: StoreGetString,
+    store: StoreSetInt64,
+) {
+    let timestamp = match block.block_time.as_ref() {
+        Some(block_time) => block_time.timestamp,
+        None => return,
+    };
+
+    let mut sales_before = 0;
+    for trx in block.transactions_owned() {
+        let accounts = trx.resolved_accounts_as_strings();
+        let (meta, transaction) = match (trx.meta, trx.transaction) {
+            (Some(meta), Some(transaction)) => (meta, transaction),
+            _ => continue,
+        };
+        let tx_id = match transaction.signatures.first() {
+            Some(signature) => bs58::encode(signature).into_string(),
+            None => continue,
+        };
+        let sales_count = nft_trades
+            .data
+            .iter()
+            .filter(|td| td.tx_id == tx_id)
+            .count();
+        if sales_count > 0 {
+            sales_before += sales_count as u64;
+            continue;
+        }
+        if meta.err.is_some() {
+            continue;
+        }
+        let msg = match transaction.message {
+            Some(msg) => msg,
+            None => continue,
+        };
+
+        let instructions = msg
+            .instructions
+            .iter()
+            .map(|inst| (inst.program_id_index, &inst.data, &inst.accounts))
+            .chain(
+                meta.inner_instructions
+                    .iter()
+                    .flat_map(|inner_instruction| inner_instruction.instructions.iter())
+                    .map(|inst| (inst.program_id_index, &inst.data, &inst.accounts)),
+            );
+
+        for (program_id_index, data, account_indices) in instructions {
+            let program_id = match accounts.get(program_id_index as usize) {
+                Some(program_id) => program_id,
+                None => continue,
+            };
+            let (from, to) = match get_transfer(&accounts, &meta, program_id, data, account_indices)
+            {
+                Some(transfer) => transfer,
+                None => continue,
+            };
+            if history_store.get_last(format!("trader:{}", from)).is_none() {
+                continue;
+            }
+
+            store.set(sales_before, format!("{}:{}", from, to), &timestamp);
+        }
+    }
+}
+
+#[substreams::handlers::map]
+fn map_wash_trades(
+    nft_trades: NftOutput,
+    history_store: StoreGetString,
+    fundings_store: StoreGetInt64,
+) -> Result<Output, substreams::errors::Error> {
+    let mut data: Vec<WashTradeData> = vec![];
+
+    for (ordinal, td) in get_successful_trades(&nft_trades) {
+        let same_wallet = td.buyer == td.seller;
+
+        // A -> B -> A: the buyer sold this mint to the current seller recently. The history
+        // is read as of this sale, so the first leg is not flagged by a later one in the
+        // same block.
+        let round_trip = history_store
+            .get_at(ordinal, format!("sold:{}:{}", td.mint, td.buyer))
+            .and_then(|value| {
+                let (counterparty, block_time) = value.rsplit_once(':')?;
+                let block_time = block_time.parse::<i64>().ok()?;
+                Some(
+                    counterparty == td.seller
+                        && td.block_time - block_time <= ROUND_TRIP_WINDOW_SECONDS,
+                )
+            })
+            .unwrap_or(false);
+
+        let seller_funded_buyer = !same_wallet
+            && fundings_store
+                .get_at(ordinal, format!("{}:{}", td.seller, td.buyer))
+                .map_or(false, |block_time| {
+                    td.block_time - block_time <= FUNDING_WINDOW_SECONDS
+                });
+
+        let zero_fee_loop = is_zero_fee(td) && (same_wallet || round_trip);
+
+        data.push(WashTradeData {
+            block_date: td.block_date.to_string(),
+            block_time: td.block_time,
+            block_slot: td.block_slot,
+            tx_id: td.tx_id.to_string(),
+            platform: td.platform.to_string(),
+            mint: td.mint.to_string(),
+            collection_mint: td.collection_mint.to_string(),
+            buyer: td.buyer.to_string(),
+            seller: td.seller.to_string(),
+            amount: td.amount,
+            currency_mint: td.currency_mint.to_string(),
+            same_wallet,
+            round_trip,
+            seller_funded_buyer,
+            zero_fee_loop,
+            suspicious: same_wallet || round_trip || seller_funded_buyer || zero_fee_loop,
+        });
+    }
+
+    Ok(Output { data })
+}
+
+#[substreams::handlers::store]
+fn store_wash_volume(wash_trades: Output, store: StoreAddFloat64) {
+    for (ordinal, trade) in get_collection_sales(&wash_trades) {
+        let key = get_key(trade);
+        let amount = trade.amount / LAMPORTS_PER_SOL;
+        store.add(ordinal, format!("volume:{}", key), amount);
+        if trade.suspicious {
+            store.add(ordinal, format!("wash:{}", key), amount);
+        }
+    }
+}
+
+#[substreams::handlers::map]
+fn map_collection_wash_ratio(
+    wash_trades: Output,
+    volume_store: StoreGetFloat64,
+) -> Result<CollectionWashVolumes, substreams::errors::Error> {
+    let mut data: Vec<CollectionWashVolume> = vec![];
+
+    for (_, trade) in get_collection_sales(&wash_trades) {
+        let key = get_key(trade);
+        let volume = volume_store
+            .get_last(format!("volume:{}", key))
+            .unwrap_or(0.0);
+        let wash_volume = volume_store
+            .get_last(format!("wash:{}", key))
+            .unwrap_or(0.0);
+
+        let row = CollectionWashVolume {
+            block_date: trade.block_date.to_string(),
+            block_time: trade.block_time,
+            block_slot: trade.block_slot,
+            collection_mint: trade.collection_mint.to_string(),
+            volume,
+            wash_volume,
+            wash_ratio: if volume > 0.0 {
+                wash_volume / volume
+            } else {
+                0.0
+            },
+        };
+
+        // One row per collection and day, with the totals after this block.
+        match data.iter().position(|other| {
+            other.block_date == row.block_date && other.collection_mint == row.collection_mint
+        }) {
+            Some(position) => data[position] = row,
+            None => data.push(row),
+        }
+    }
+
+    Ok(CollectionWashVolumes { data })
+}
+
+// Failed transactions still decode into trades, with the amounts of their instruction
+// args. Ordinals are the trades' positions in the nft trades output.
+fn get_successful_trades(nft_trades: &NftOutput) -> Vec<(u64, &TradeData)> {
+    nft_trades
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, td)| td.success)
+        .map(|(ordinal, td)| (ordinal as u64, td))
+        .collect()
+}
+
+fn get_collection_sales(wash_trades: &Output) -> Vec<(u64, &WashTradeData)> {
+    wash_trades
+        .data
+        .iter()
+        .enumerate()
+        .filter(|(_, trade)| {
+            !trade.collection_mint.is_empty()
+                && trade.currency_mint == WSOL_MINT
+                && trade.amount > 0.0
+        })
+        .map(|(ordinal, trade)| (ordinal as u64, trade))
+        .collect()
+}
+
+// Lamports sent with a system transfer, or a payment currency sent with a token program
+// transfer. Other tokens, the traded items first, are not funding. Token accounts are
+// resolved to their owners, and a transfer with seed is sent by the base wallet the
+// source account derives from.
+fn get_transfer(
+    accounts: &[String],
+    meta: &TransactionStatusMeta,
+    program_id: &str,
+    data: &[u8],
+    account_indices: &[u8],
+) -> Option<(String, String)> {
+    match program_id {
+        SYSTEM_PROGRAM_ADDRESS => {
+            let instruction = u32::from_le_bytes(data.get(0..4)?.try_into().ok()?);
+            let (from, to) = match instruction {
+                SYSTEM_TRANSFER_INSTRUCTION => (0, 1),
+                SYSTEM_TRANSFER_WITH_SEED_INSTRUCTION => (1, 2),
+                _ => return None,
+            };
+            Some((
+                get_account(accounts, account_indices, from)?,
+                get_account(accounts, account_indices, to)?,
+            ))
+        }
+        TOKEN_PROGRAM_ADDRESS | TOKEN_2022_PROGRAM_ADDRESS => {
+            let (source, destination, authority) = match *data.first()? {
+                TOKEN_TRANSFER_INSTRUCTION => (0, 1, 2),
+                TOKEN_TRANSFER_CHECKED_INSTRUCTION => (0, 2, 3),
+                _ => return None,
+            };
+            let source = get_token_balance(meta, *account_indices.get(source)?);
+            let destination = get_token_balance(meta, *account_indices.get(destination)?)?;
+            if destination.mint != WSOL_MINT && destination.mint != USDC_MINT {
+                return None;
+            }
+            let from = match source {
+                Some(source) => source.owner.to_string(),
+                None => get_account(accounts, account_indices, authority)?,
+            };
+            Some((from, destination.owner.to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn get_account(accounts: &[String], account_indices: &[u8], position: usize) -> Option<String> {
+    accounts
+        .get(*account_indices.get(position)? as usize)
+        .map(|account| account.to_string())
+}
+
+fn get_token_balance(meta: &TransactionStatusMeta, account_index: u8) -> Option<&TokenBalance> {
+    meta.post_token_balances
+        .iter()
+        .chain(meta.pre_token_balances.iter())
+        .find(|balance| balance.account_index == account_index as u32 && !balance.owner.is_empty())
+}
+
+fn is_zero_fee(td: &TradeData) -> bool {
+    td.taker_fee == 0.0 && td.maker_fee == 0.0 && td.amm_fee == 0.0
+}
+
+fn get_key(trade: &WashTradeData) -> String {
+    format!("{}:{}", trade.block_date, trade.collection_mint)
+}
@@ -0,0 +1,23 @@
+// @generated
+pub mod sf {
+    pub mod solana {
+        pub mod nft {
+            pub mod trades {
+                // @@protoc_insertion_point(attribute:sf.solana.nft.trades.v1)
+                pub mod v1 {
+                    include!("../../../nft-trades/src/pb/sf.solana.nft.trades.v1.rs");
+                    // @@protoc_insertion_point(sf.solana.nft.trades.v1)
+                }
+            }
+            pub mod wash {
+                pub mod trading {
+                    // @@protoc_insertion_point(attribute:sf.solana.nft.wash.trading.v1)
+                    pub mod v1 {
+                        include!("sf.solana.nft.wash.trading.v1.rs");
+                        // @@protoc_insertion_point(sf.solana.nft.wash.trading.v1)
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// @generated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Output {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<WashTradeData>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WashTradeData {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="2")]
+    pub block_time: i64,
+    #[prost(uint64, required, tag="3")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="4")]
+    pub tx_id: ::prost::alloc::string::String,
+    #[prost(string, required, tag="5")]
+    pub platform: ::prost::alloc::string::String,
+    #[prost(string, required, tag="6")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(string, required, tag="7")]
+    pub collection_mint: ::prost::alloc::string::String,
+    #[prost(string, required, tag="8")]
+    pub buyer: ::prost::alloc::string::String,
+    #[prost(string, required, tag="9")]
+    pub seller: ::prost::alloc::string::String,
+    #[prost(double, required, tag="10")]
+    pub amount: f64,
+    #[prost(string, required, tag="11")]
+    pub currency_mint: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="12")]
+    pub same_wallet: bool,
+    #[prost(bool, required, tag="13")]
+    pub round_trip: bool,
+    #[prost(bool, required, tag="14")]
+    pub seller_funded_buyer: bool,
+    #[prost(bool, required, tag="15")]
+    pub zero_fee_loop: bool,
+    #[prost(bool, required, tag="16")]
+    pub suspicious: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectionWashVolumes {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<CollectionWashVolume>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CollectionWashVolume {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="2")]
+    pub block_time: i64,
+    #[prost(uint64, required, tag="3")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="4")]
+    pub collection_mint: ::prost::alloc::string::String,
+    #[prost(double, required, tag="5")]
+    pub volume: f64,
+    #[prost(double, required, tag="6")]
+    pub wash_volume: f64,
+    #[prost(double, required, tag="7")]
+    pub wash_ratio: f64,
+}
+// @@protoc_insertion_point(module)
@@ -0,0 +1,213 @@
+use substreams::pb::substreams::store_delta::Operation;
+use substreams::pb::substreams::Clock;
+use substreams::store::{
+    DeltaInt64, Deltas, StoreAdd, StoreAddFloat64, StoreAddInt64, StoreDelete, StoreGet,
+    StoreGetFloat64, StoreGetInt64, StoreMax, StoreMaxInt64, StoreNew,
+};
+
+use crate::pb::sf::solana::program_analysis::v1::{
+    Output, ProgramDailyStat, ProgramDailyStats, ProgramStats,
+};
+use crate::utils::convert_to_date;
+
+// Unique wallets are counted with a HyperLogLog sketch of 2^8 registers per program and
+// day, about 6.5% standard error for a fixed 256 keys instead of one key per wallet.
+const HLL_PRECISION: u32 = 8;
+const HLL_REGISTERS: usize = 1 << HLL_PRECISION;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+const COUNTERS: [&str; 10] = [
+    "fee_lamports",
+    "base_fee_lamports",
+    "priority_fee_lamports",
+    "compute_units_consumed",
+    "compute_units_allocated",
+    "successful_txns_count",
+    "failed_txns_count",
+    "total_outer_invocation_count",
+    "total_inner_invocation_count",
+    "failed_invocation_count",
+];
+
+#[substreams::handlers::store]
+fn store_program_daily_counters(clock: Clock, output: Output, store: StoreAddInt64) {
+    store.delete_prefix(0, &format!("{}:", get_expired_date(&clock)));
+
+    for (ordinal, stats) in output.data.iter().enumerate() {
+        let values = [
+            stats.fee_lamports,
+            stats.base_fee_lamports,
+            stats.priority_fee_lamports,
+            stats.compute_units_consumed,
+            stats.compute_units_allocated,
+            stats.successful_txns_count,
+            stats.failed_txns_count,
+            stats.total_outer_invocation_count,
+            stats.total_inner_invocation_count,
+            stats.failed_invocation_count,
+        ];
+        for (name, value) in COUNTERS.iter().zip(values) {
+            store.add(ordinal as u64, get_key(stats, name), value as i64);
+        }
+    }
+}
+
+#[substreams::handlers::store]
+fn store_program_daily_wallets(clock: Clock, output: Output, store: StoreMaxInt64) {
+    store.delete_prefix(0, &format!("{}:", get_expired_date(&clock)));
+
+    for (ordinal, stats) in output.data.iter().enumerate() {
+        let wallets = [
+            ("signers", &stats.signers),
+            ("fee_payers", &stats.fee_payers),
+        ];
+        for (name, addresses) in wallets {
+            for address in addresses.iter() {
+                let (register, rank) = get_register(address);
+                store.max(
+                    ordinal as u64,
+                    format!("{}:{}", get_key(stats, name), register),
+                    rank,
+                );
+            }
+        }
+    }
+}
+
+// The estimate only needs the sum of 2^-rank over the registers and how many are still
+// empty. Both are kept up to date from the register changes, so reading a sketch takes
+// two keys instead of all of its registers.
+#[substreams::handlers::store]
+fn store_program_daily_sketches(
+    clock: Clock,
+    wallets_deltas: Deltas<DeltaInt64>,
+    store: StoreAddFloat64,
+) {
+    store.delete_prefix(0, &format!("{}:", get_expired_date(&clock)));
+
+    for delta in wallets_deltas.deltas {
+        let old_rank = match delta.operation {
+            Operation::Create => 0,
+            Operation::Update => delta.old_value,
+            _ => continue,
+        };
+        if delta.new_value <= old_rank {
+            continue;
+        }
+        let sketch = match delta.key.rsplit_once(':') {
+            Some((sketch, _)) => sketch,
+            None => continue,
+        };
+        if old_rank == 0 {
+            store.add(delta.ordinal, format!("{}:filled", sketch), 1.0);
+        }
+        store.add(
+            delta.ordinal,
+            format!("{}:harmonic_sum", sketch),
+            2f64.powi(-(delta.new_value as i32)) - 2f64.powi(-(old_rank as i32)),
+        );
+    }
+}
+
+// Totals for the day so far, for every program active in this block.
+#[substreams::handlers::map]
+fn map_program_daily_stats(
+    output: Output,
+    counters_store: StoreGetInt64,
+    sketches_store: StoreGetFloat64,
+) -> Result<ProgramDailyStats, substreams::errors::Error> {
+    let mut data: Vec<ProgramDailyStat> = vec![];
+
+    for stats in output.data.iter() {
+        let get_counter =
+            |name: &str| counters_store.get_last(get_key(stats, name)).unwrap_or(0) as u64;
+        let get_unique = |name: &str| {
+            let sketch = get_key(stats, name);
+            let get_value = |field: &str| {
+                sketches_store
+                    .get_last(format!("{}:{}", sketch, field))
+                    .unwrap_or(0.0)
+            };
+            // Empty registers have a rank of 0 and count for 2^0 in the sum.
+            let empty = HLL_REGISTERS as f64 - get_value("filled");
+            estimate_cardinality(get_value("harmonic_sum") + HLL_REGISTERS as f64, empty)
+        };
+
+        data.push(ProgramDailyStat {
+            block_date: stats.block_date.to_string(),
+            program_id: stats.program_id.to_string(),
+            fee_lamports: get_counter("fee_lamports"),
+            base_fee_lamports: get_counter("base_fee_lamports"),
+            priority_fee_lamports: get_counter("priority_fee_lamports"),
+            compute_units_consumed: get_counter("compute_units_consumed"),
+            compute_units_allocated: get_counter("compute_units_allocated"),
+            successful_txns_count: get_counter("successful_txns_count"),
+            failed_txns_count: get_counter("failed_txns_count"),
+            total_outer_invocation_count: get_counter("total_outer_invocation_count"),
+            total_inner_invocation_count: get_counter("total_inner_invocation_count"),
+            failed_invocation_count: get_counter("failed_invocation_count"),
+            unique_signers: get_unique("signers"),
+            unique_fee_payers: get_unique("fee_payers"),
+        });
+    }
+
+    Ok(ProgramDailyStats { data })
+}
+
+// Keys lead with the day so the previous day can be dropped with a single prefix.
+fn get_key(stats: &ProgramStats, name: &str) -> String {
+    format!("{}:{}:{}", stats.block_date, name, stats.program_id)
+}
+
+// Only the current day is read, the previous one is dropped on every block.
+fn get_expired_date(clock: &Clock) -> String {
+    let timestamp = clock
+        .timestamp
+        .as_ref()
+        .map_or(0, |timestamp| timestamp.seconds);
+    convert_to_date(timestamp - SECONDS_PER_DAY).unwrap_or_default()
+}
+
+// The first bits of the hash pick the register, the rank is the position of the first
+// set bit in the rest.
+fn get_register(address: &str) -> (usize, i64) {
+    let hash = hash_address(address);
+    let register = (hash >> (64 - HLL_PRECISION)) as usize;
+    let rest = hash << HLL_PRECISION;
+    let rank = (rest.leading_zeros().min(64 - HLL_PRECISION) + 1) as i64;
+    (register, rank)
+}
+
+// Keys are already close to uniform, mixing the decoded bytes guards against vanity
+// prefixes.
+fn hash_address(address: &str) -> u64 {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .unwrap_or_else(|_| address.as_bytes().to_vec());
+    bytes.chunks(8).fold(0, |hash, chunk| {
+        let mut word = [0u8; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        splitmix64(hash ^ u64::from_le_bytes(word))
+    })
+}
+
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn estimate_cardinality(sum: f64, empty: f64) -> u64 {
+    let m = HLL_REGISTERS as f64;
+    let alpha = 0.7213 / (1.0 + 1.079 / m);
+    let estimate = alpha * m * m / sum;
+
+    // Small cardinalities are better estimated from the share of empty registers.
+    if estimate <= 2.5 * m && empty > 0.0 {
+        (m * (m / empty).ln()).round() as u64
+    } else {
+        estimate.round() as u64
+    }
+}
@@ -1,3 +1,4 @@
+mod daily_stats;
 mod pb;
 mod utils;
 
@@ -9,10 +10,11 @@ use substreams::log;
 use substreams_solana::pb::sf::solana::r#type::v1::Block;
 use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
 
+use log_tree_parser::parse_logs;
+use log_tree_parser::LogContext;
+use program_errors::decode_error;
 use substreams_solana::pb::sf::solana::r#type::v1::TransactionStatusMeta;
 use utils::convert_to_date;
-use utils::parse_logs;
-use utils::LogContext;
 
 const VOTE_ACCOUNT: &str = "Vote111111111111111111111111111111111111111";
 
@@ -20,17 +22,18 @@ const VOTE_ACCOUNT: &str = "Vote111111111111111111111111111111111111111";
 struct ProgramData {
     signers: HashSet<String>,
     fee_payers: HashSet<String>,
-    fee_lamports: u32,
-    base_fee_lamports: u32,
-    priority_fee_lamports: u32,
-    compute_units_consumed: u32,
-    compute_units_allocated: u32,
-    successful_txns_count: u32,
-    failed_txns_count: u32,
-    outer_invocation_count: u32,
-    inner_invocation_count: u32,
-    failed_invocation_count: u32,
+    fee_lamports: u64,
+    base_fee_lamports: u64,
+    priority_fee_lamports: u64,
+    compute_units_consumed: u64,
+    compute_units_allocated: u64,
+    successful_txns_count: u64,
+    failed_txns_count: u64,
+    outer_invocation_count: u64,
+    inner_invocation_count: u64,
+    failed_invocation_count: u64,
     errors: HashMap<String, u32>,
+    decoded_errors: HashMap<String, u32>,
 }
 
 impl ProgramData {
@@ -49,29 +52,51 @@ impl ProgramData {
             inner_invocation_count: 0,
             failed_invocation_count: 0,
             errors: HashMap::new(),
+            decoded_errors: HashMap::new(),
         }
     }
 
     fn update_with_log(&mut self, log: &LogContext) {
-        self.compute_units_consumed += log.consumed_units as u32;
-        self.compute_units_allocated += log.compute_units as u32;
+        self.compute_units_consumed += log.consumed_units as u64;
+        self.compute_units_allocated += log.compute_units as u64;
 
         if let Some(ref error) = log.failure_message {
             self.failed_invocation_count += 1;
-            *self.errors.entry(error.clone()).or_insert(0) += 1;
+            self.add_error(log, error);
+        }
+    }
+
+    // Custom errors are also counted under the name and message from the program's IDL,
+    // unless the program only passed on the failure of one of its CPIs.
+    fn add_error(&mut self, log: &LogContext, error: &str) {
+        *self.errors.entry(error.to_string()).or_insert(0) += 1;
+
+        let from_cpi = log
+            .children_nodes
+            .iter()
+            .any(|child| child.failure_message.is_some());
+        if from_cpi {
+            return;
+        }
+        if let Some(program_error) = decode_error(&log.program_id, error) {
+            let mut decoded_error = format!("{}: {}", program_error.name, program_error.message);
+            if program_error.guessed {
+                decoded_error.push_str(" (guessed)");
+            }
+            *self.decoded_errors.entry(decoded_error).or_insert(0) += 1;
         }
     }
 
     fn new_from_log(log: &LogContext) -> Self {
         let mut new_data = ProgramData::new();
 
-        new_data.compute_units_consumed = log.consumed_units as u32;
-        new_data.compute_units_allocated = log.compute_units as u32;
+        new_data.compute_units_consumed = log.consumed_units as u64;
+        new_data.compute_units_allocated = log.compute_units as u64;
 
         new_data.failed_invocation_count = if log.failure_message.is_some() { 1 } else { 0 };
 
         if let Some(ref error) = log.failure_message {
-            new_data.errors.insert(error.clone(), 1);
+            new_data.add_error(log, error);
         }
 
         new_data
@@ -90,10 +115,9 @@ impl ProgramData {
             self.signers.extend(signers.iter().cloned());
         }
 
-        self.fee_lamports = fees as u32;
-        self.base_fee_lamports = 5000 * num_required_signatures;
-        self.priority_fee_lamports =
-            fees.saturating_sub(5000 * num_required_signatures as u64) as u32;
+        self.fee_lamports = fees;
+        self.base_fee_lamports = 5000 * num_required_signatures as u64;
+        self.priority_fee_lamports = fees.saturating_sub(5000 * num_required_signatures as u64);
         self.successful_txns_count = if meta.err.is_none() { 1 } else { 0 };
         self.failed_txns_count = if meta.err.is_some() { 1 } else { 0 };
     }
@@ -261,6 +285,12 @@ fn convert_to_output(
                     for (error, count) in &program_data.errors {
                         *existing_stats.errors.entry(error.clone()).or_insert(0) += count;
                     }
+                    for (error, count) in &program_data.decoded_errors {
+                        *existing_stats
+                            .decoded_errors
+                            .entry(error.clone())
+                            .or_insert(0) += count;
+                    }
                 })
                 .or_insert_with(|| ProgramStats {
                     block_date: block_date.to_string(),
@@ -282,6 +312,7 @@ fn convert_to_output(
 
                     //handle errors
                     errors: program_data.errors.clone(),
+                    decoded_errors: program_data.decoded_errors.clone(),
                 });
         }
     }
@@ -0,0 +1,159 @@
+// Registry of program errors built from the `errors` section of program IDLs, used to
+// turn failures like "custom program error: 0x1794" into the error name and message
+// the program declares.
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+// Keyed by program id, not every IDL records its address.
+const IDLS: [(&str, &str); 8] = [
+    (
+        "whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc",
+        include_str!("../idls/whirlpool.json"),
+    ),
+    (
+        "CAMMCzo5YL8w4VFF8KVHrK22GGUsp5VTaW7grrKgrWqK",
+        include_str!("../idls/raydium_clmm.json"),
+    ),
+    (
+        "LBUZKhRxPF3XUpBCjp4YzTKgLccjZhTSDM9YuVaPwxo",
+        include_str!("../idls/meteora_dlmm.json"),
+    ),
+    (
+        "24Uqj9JCLxUeoC3hGfh5W3s9FM9uCHDS2SG3LYwBpyTi",
+        include_str!("../../meteora/vaults/idls/vaults.json"),
+    ),
+    (
+        "DCA265Vj8a9CEuX1eb1LWRnDT7uK6q1xMipnNyatn23M",
+        include_str!("../idls/jupiter_dca.json"),
+    ),
+    (
+        "JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4",
+        include_str!("../idls/jupiter_aggregator_v6.json"),
+    ),
+    (
+        "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA",
+        include_str!("../idls/spl_token.json"),
+    ),
+    (
+        "11111111111111111111111111111111",
+        include_str!("../idls/system_program.json"),
+    ),
+];
+
+// Errors raised by the Anchor framework itself, below the 6000 range of custom errors.
+const ANCHOR_IDL: &str = include_str!("../idls/anchor.json");
+
+const CUSTOM_ERROR_PREFIX: &str = "custom program error: 0x";
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ProgramError {
+    pub code: u32,
+    pub name: String,
+    #[serde(rename = "msg", default)]
+    pub message: String,
+    // Set when the program is unknown and the code was only assumed to be Anchor's.
+    #[serde(skip)]
+    pub guessed: bool,
+}
+
+#[derive(Deserialize)]
+struct Idl {
+    metadata: Option<IdlMetadata>,
+    #[serde(default)]
+    errors: Vec<ProgramError>,
+}
+
+#[derive(Deserialize)]
+struct IdlMetadata {
+    // Native programs don't go through Anchor, their low codes are their own.
+    #[serde(default)]
+    native: bool,
+}
+
+struct ProgramErrors {
+    native: bool,
+    errors: HashMap<u32, ProgramError>,
+}
+
+struct Registry {
+    programs: HashMap<String, ProgramErrors>,
+    anchor_errors: HashMap<u32, ProgramError>,
+    anchor_guesses: HashMap<u32, ProgramError>,
+}
+
+fn parse_idl(idl: &str) -> Idl {
+    serde_json::from_str(idl).expect("Failed to parse IDL errors")
+}
+
+fn index_errors(errors: Vec<ProgramError>) -> HashMap<u32, ProgramError> {
+    errors
+        .into_iter()
+        .map(|error| (error.code, error))
+        .collect()
+}
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(|| {
+        let mut programs = HashMap::new();
+        for (address, idl) in IDLS.iter() {
+            let idl = parse_idl(idl);
+            programs.insert(
+                address.to_string(),
+                ProgramErrors {
+                    native: idl.metadata.is_some_and(|metadata| metadata.native),
+                    errors: index_errors(idl.errors),
+                },
+            );
+        }
+
+        let anchor_errors = index_errors(parse_idl(ANCHOR_IDL).errors);
+        let anchor_guesses = anchor_errors
+            .iter()
+            .map(|(code, error)| {
+                let error = ProgramError {
+                    guessed: true,
+                    ..error.clone()
+                };
+                (*code, error)
+            })
+            .collect();
+
+        Registry {
+            programs,
+            anchor_errors,
+            anchor_guesses,
+        }
+    })
+}
+
+// Unknown programs fall back to the Anchor framework errors, most programs on mainnet
+// are built with it. Nothing says they are, so those results are marked as guessed.
+pub fn get_program_error(program_id: &str, code: u32) -> Option<&'static ProgramError> {
+    let registry = registry();
+    match registry.programs.get(program_id) {
+        Some(program) if program.native => program.errors.get(&code),
+        Some(program) => program
+            .errors
+            .get(&code)
+            .or_else(|| registry.anchor_errors.get(&code)),
+        None => registry.anchor_guesses.get(&code),
+    }
+}
+
+// Reads the code out of a "Program <id> failed: custom program error: 0x1771" message.
+pub fn parse_error_code(failure_message: &str) -> Option<u32> {
+    let hex = failure_message
+        .find(CUSTOM_ERROR_PREFIX)
+        .map(|start| &failure_message[start + CUSTOM_ERROR_PREFIX.len()..])?;
+    let end = hex
+        .find(|c: char| !c.is_ascii_hexdigit())
+        .unwrap_or(hex.len());
+    u32::from_str_radix(&hex[..end], 16).ok()
+}
+
+pub fn decode_error(program_id: &str, failure_message: &str) -> Option<&'static ProgramError> {
+    get_program_error(program_id, parse_error_code(failure_message)?)
+}
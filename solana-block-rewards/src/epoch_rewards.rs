@@ -0,0 +1,96 @@
+use epoch_schedule::SLOTS_PER_EPOCH;
+use substreams::store::{
+    StoreGet, StoreGetInt64, StoreNew, StoreSetIfNotExists, StoreSetIfNotExistsInt64,
+};
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use crate::pb::sf::solana::block::rewards::v1::{EpochReward, EpochRewards};
+use crate::pb::sf::solana::r#type::v1::RewardType;
+use crate::utils::convert_to_date;
+
+// Used when the start of the rewarded epoch was not streamed.
+const NOMINAL_EPOCH_SECONDS: i64 = SLOTS_PER_EPOCH as i64 * 400 / 1000;
+const SECONDS_PER_YEAR: f64 = 365.25 * 86_400.0;
+// Only blocks this close to the boundary mark an epoch start, so a stream starting
+// mid-epoch falls back to the nominal length instead of a truncated one.
+const EPOCH_START_MAX_SLOTS: u64 = 1_000;
+
+#[substreams::handlers::store]
+fn store_epoch_start_times(block: Block, store: StoreSetIfNotExistsInt64) {
+    if block.slot % SLOTS_PER_EPOCH >= EPOCH_START_MAX_SLOTS {
+        return;
+    }
+    if let Some(block_time) = block.block_time.as_ref() {
+        store.set_if_not_exists(
+            0,
+            (block.slot / SLOTS_PER_EPOCH).to_string(),
+            &block_time.timestamp,
+        );
+    }
+}
+
+// Staking and voting rewards for an epoch are paid in the first blocks of the next one,
+// each account gets a single reward per epoch. The stake a staking reward was earned on
+// is the balance before the reward was credited.
+#[substreams::handlers::map]
+fn map_epoch_rewards(
+    block: Block,
+    start_times_store: StoreGetInt64,
+) -> Result<EpochRewards, substreams::errors::Error> {
+    let mut data: Vec<EpochReward> = vec![];
+
+    let block_time = block
+        .block_time
+        .as_ref()
+        .map_or(0, |block_time| block_time.timestamp);
+    let block_epoch = block.slot / SLOTS_PER_EPOCH;
+    if block_epoch == 0 {
+        return Ok(EpochRewards { data });
+    }
+    let epoch = block_epoch - 1;
+
+    let epoch_duration = match (
+        start_times_store.get_last(epoch.to_string()),
+        start_times_store.get_last(block_epoch.to_string()),
+    ) {
+        (Some(start), Some(end)) if end > start => end - start,
+        _ => NOMINAL_EPOCH_SECONDS,
+    };
+
+    for reward in block.rewards.iter() {
+        let reward_type = match RewardType::from_i32(reward.reward_type) {
+            Some(RewardType::Staking) => "Staking",
+            Some(RewardType::Voting) => "Voting",
+            _ => continue,
+        };
+
+        // A vote account's balance is not delegated stake, commission rewards have no
+        // meaningful rate.
+        let stake = match reward_type {
+            "Staking" => (reward.post_balance as i64 - reward.lamports).max(0) as u64,
+            _ => 0,
+        };
+        let apr = if stake > 0 {
+            reward.lamports as f64 / stake as f64 * SECONDS_PER_YEAR / epoch_duration as f64
+        } else {
+            0.0
+        };
+
+        data.push(EpochReward {
+            epoch,
+            block_slot: block.slot,
+            block_time: block_time as u64,
+            block_date: convert_to_date(block_time).unwrap_or_else(|_| "2020-03-15".to_string()),
+            pubkey: reward.pubkey.to_string(),
+            reward_type: reward_type.to_string(),
+            lamports: reward.lamports,
+            post_balance: reward.post_balance,
+            stake,
+            commission: reward.commission.parse::<u32>().unwrap_or(0),
+            epoch_duration,
+            apr,
+        });
+    }
+
+    Ok(EpochRewards { data })
+}
@@ -1,3 +1,4 @@
+mod epoch_rewards;
 mod pb;
 mod utils;
 
@@ -25,4 +25,38 @@ pub struct BlockReward {
     #[prost(string, required, tag="8")]
     pub commission: ::prost::alloc::string::String,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EpochRewards {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<EpochReward>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EpochReward {
+    #[prost(uint64, required, tag="1")]
+    pub epoch: u64,
+    #[prost(uint64, required, tag="2")]
+    pub block_slot: u64,
+    #[prost(uint64, required, tag="3")]
+    pub block_time: u64,
+    #[prost(string, required, tag="4")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(string, required, tag="5")]
+    pub pubkey: ::prost::alloc::string::String,
+    #[prost(string, required, tag="6")]
+    pub reward_type: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="7")]
+    pub lamports: i64,
+    #[prost(uint64, required, tag="8")]
+    pub post_balance: u64,
+    #[prost(uint64, required, tag="9")]
+    pub stake: u64,
+    #[prost(uint32, required, tag="10")]
+    pub commission: u32,
+    #[prost(int64, required, tag="11")]
+    pub epoch_duration: i64,
+    #[prost(double, required, tag="12")]
+    pub apr: f64,
+}
 // @@protoc_insertion_point(module)
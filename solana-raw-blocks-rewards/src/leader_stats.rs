@@ -0,0 +1,177 @@
+use epoch_schedule::SLOTS_PER_EPOCH;
+use substreams::store::{
+    StoreAdd, StoreAddInt64, StoreDelete, StoreGet, StoreGetInt64, StoreGetString, StoreNew,
+    StoreSet, StoreSetString,
+};
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use crate::pb::sf::solana::{
+    r#type::v1::RewardType,
+    raw::blocks::rewards::v1::{LeaderBlock, LeaderBlocks, LeaderEpochStats, LeaderStats},
+};
+use crate::utils::convert_to_date;
+
+// Leaders get windows of four consecutive slots, aligned on the epoch start.
+const LEADER_WINDOW_SLOTS: u64 = 4;
+
+// The block's fee reward is paid to the identity of the validator that produced it.
+#[substreams::handlers::map]
+fn map_leader_blocks(block: Block) -> Result<LeaderBlocks, substreams::errors::Error> {
+    let fee_reward = block
+        .rewards
+        .iter()
+        .find(|reward| reward.reward_type == RewardType::Fee as i32);
+
+    let leader_block = match fee_reward {
+        Some(fee_reward) => {
+            let block_time = block
+                .block_time
+                .as_ref()
+                .map_or(0, |block_time| block_time.timestamp);
+            let skipped_slots = block.slot.saturating_sub(block.parent_slot + 1);
+
+            // Only the gap slots that share this block's window were the leader's own, the
+            // ones left in the parent's window are charged to its leader by the stats store.
+            let window_start = block.slot - block.slot % LEADER_WINDOW_SLOTS;
+            let leader_skipped_slots = (block.slot - skipped_slots..block.slot)
+                .filter(|slot| *slot >= window_start)
+                .count() as u64;
+
+            vec![LeaderBlock {
+                block_date: convert_to_date(block_time)
+                    .unwrap_or_else(|_| "2020-03-15".to_string()),
+                block_slot: block.slot,
+                block_timestamp: block_time as u64,
+                parent_slot: block.parent_slot,
+                epoch: block.slot / SLOTS_PER_EPOCH,
+                leader: fee_reward.pubkey.to_string(),
+                fees: fee_reward.lamports,
+                transactions: block.transactions.len() as u64,
+                skipped_slots,
+                leader_skipped_slots,
+            }]
+        }
+        None => vec![],
+    };
+
+    Ok(LeaderBlocks { data: leader_block })
+}
+
+// Leader of each produced block, kept for the current and previous epoch only.
+#[substreams::handlers::store]
+fn store_block_leaders(leader_blocks: LeaderBlocks, store: StoreSetString) {
+    for (ordinal, leader_block) in leader_blocks.data.iter().enumerate() {
+        if leader_block.epoch >= 2 {
+            store.delete_prefix(0, &format!("{}:", leader_block.epoch - 2));
+        }
+        store.set(
+            ordinal as u64,
+            get_block_key(leader_block.block_slot),
+            &leader_block.leader,
+        );
+    }
+}
+
+// Gap slots are charged to the leader of the window they fall in when that leader is
+// known: this block's leader for its own window, the parent's leader for the rest of the
+// parent's window. Windows skipped entirely have no block and no known leader, they only
+// count towards the epoch total.
+#[substreams::handlers::store]
+fn store_leader_stats(
+    leader_blocks: LeaderBlocks,
+    leaders_store: StoreGetString,
+    store: StoreAddInt64,
+) {
+    for (ordinal, leader_block) in leader_blocks.data.iter().enumerate() {
+        let ordinal = ordinal as u64;
+        let key = format!("{}:{}", leader_block.epoch, leader_block.leader);
+
+        let parent_window_end = leader_block.parent_slot
+            - leader_block.parent_slot % LEADER_WINDOW_SLOTS
+            + LEADER_WINDOW_SLOTS;
+        let window_start = leader_block.block_slot - leader_block.block_slot % LEADER_WINDOW_SLOTS;
+        let parent_skipped_slots = parent_window_end
+            .min(window_start)
+            .saturating_sub(leader_block.parent_slot + 1);
+        if parent_skipped_slots > 0 {
+            if let Some(parent_leader) =
+                leaders_store.get_last(get_block_key(leader_block.parent_slot))
+            {
+                store.add(
+                    ordinal,
+                    format!(
+                        "skipped:{}:{}",
+                        leader_block.parent_slot / SLOTS_PER_EPOCH,
+                        parent_leader
+                    ),
+                    parent_skipped_slots as i64,
+                );
+            }
+        }
+
+        store.add(ordinal, format!("blocks:{}", key), 1);
+        store.add(
+            ordinal,
+            format!("skipped:{}", key),
+            leader_block.leader_skipped_slots as i64,
+        );
+        store.add(ordinal, format!("fees:{}", key), leader_block.fees);
+        store.add(
+            ordinal,
+            format!("transactions:{}", key),
+            leader_block.transactions as i64,
+        );
+
+        store.add(ordinal, format!("blocks:{}", leader_block.epoch), 1);
+        store.add(
+            ordinal,
+            format!("skipped:{}", leader_block.epoch),
+            leader_block.skipped_slots as i64,
+        );
+    }
+}
+
+fn get_block_key(slot: u64) -> String {
+    format!("{}:{}", slot / SLOTS_PER_EPOCH, slot)
+}
+
+#[substreams::handlers::map]
+fn map_leader_stats(
+    leader_blocks: LeaderBlocks,
+    stats_store: StoreGetInt64,
+) -> Result<LeaderStats, substreams::errors::Error> {
+    let mut data: Vec<LeaderEpochStats> = vec![];
+
+    for leader_block in leader_blocks.data.iter() {
+        let key = format!("{}:{}", leader_block.epoch, leader_block.leader);
+        let get_stat = |name: &str, key: &String| {
+            stats_store
+                .get_last(format!("{}:{}", name, key))
+                .unwrap_or(0)
+        };
+
+        let blocks_produced = get_stat("blocks", &key);
+        let transactions = get_stat("transactions", &key);
+        let epoch = leader_block.epoch.to_string();
+
+        data.push(LeaderEpochStats {
+            epoch: leader_block.epoch,
+            leader: leader_block.leader.to_string(),
+            block_date: leader_block.block_date.to_string(),
+            block_slot: leader_block.block_slot,
+            blocks_produced,
+            skipped_slots: get_stat("skipped", &key),
+            fees: get_stat("fees", &key),
+            transactions,
+            avg_transactions_per_block: if blocks_produced > 0 {
+                transactions as f64 / blocks_produced as f64
+            } else {
+                0.0
+            },
+            epoch_blocks_produced: get_stat("blocks", &epoch),
+            epoch_skipped_slots: get_stat("skipped", &epoch),
+        });
+    }
+
+    Ok(LeaderStats { data })
+}
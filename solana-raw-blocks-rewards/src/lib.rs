@@ -1,3 +1,4 @@
+mod leader_stats;
 mod pb;
 mod utils;
 
@@ -28,4 +28,66 @@ pub struct BlockReward {
     #[prost(string, required, tag="9")]
     pub block_hash: ::prost::alloc::string::String,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaderBlocks {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<LeaderBlock>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaderBlock {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="2")]
+    pub block_slot: u64,
+    #[prost(uint64, required, tag="3")]
+    pub block_timestamp: u64,
+    #[prost(uint64, required, tag="4")]
+    pub parent_slot: u64,
+    #[prost(uint64, required, tag="5")]
+    pub epoch: u64,
+    #[prost(string, required, tag="6")]
+    pub leader: ::prost::alloc::string::String,
+    #[prost(int64, required, tag="7")]
+    pub fees: i64,
+    #[prost(uint64, required, tag="8")]
+    pub transactions: u64,
+    #[prost(uint64, required, tag="9")]
+    pub skipped_slots: u64,
+    #[prost(uint64, required, tag="10")]
+    pub leader_skipped_slots: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaderStats {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<LeaderEpochStats>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LeaderEpochStats {
+    #[prost(uint64, required, tag="1")]
+    pub epoch: u64,
+    #[prost(string, required, tag="2")]
+    pub leader: ::prost::alloc::string::String,
+    #[prost(string, required, tag="3")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="4")]
+    pub block_slot: u64,
+    #[prost(int64, required, tag="5")]
+    pub blocks_produced: i64,
+    #[prost(int64, required, tag="6")]
+    pub skipped_slots: i64,
+    #[prost(int64, required, tag="7")]
+    pub fees: i64,
+    #[prost(int64, required, tag="8")]
+    pub transactions: i64,
+    #[prost(double, required, tag="9")]
+    pub avg_transactions_per_block: f64,
+    #[prost(int64, required, tag="10")]
+    pub epoch_blocks_produced: i64,
+    #[prost(int64, required, tag="11")]
+    pub epoch_skipped_slots: i64,
+}
 // @@protoc_insertion_point(module)
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+
+use substreams_solana::pb::sf::solana::r#type::v1::{Block, ConfirmedTransaction};
+use writable_accounts::get_writable_accounts;
+
+use crate::pb::sf::solana::raw::blocks::v1::{BundleCluster, FeeMarket, FeeMarkets};
+use crate::utils::convert_to_date;
+use crate::{get_priority_fee, VOTE_ACCOUNT};
+
+const COMPUTE_BUDGET_PROGRAM: &str = "ComputeBudget111111111111111111111111111111";
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+const SYSTEM_PROGRAM: &str = "11111111111111111111111111111111";
+const SYSTEM_TRANSFER: u32 = 2;
+
+const JITO_TIP_ACCOUNTS: [&str; 8] = [
+    "96gYZGLnJYVFmbjzopPSU6QiEV5fGqZNyN9nmNhvrZU5",
+    "HFqU5x63VTqvQss8hp11i4wVV8bD44PvwucfZ2bU7gRe",
+    "Cw8CFyM9FkoMi7K7Crf6HNQqf4uEMzpKw6QNghXLvLkY",
+    "ADaUMid9yfUytqMBgopwjb2DTLSokTSzL1zt6iGPaS49",
+    "DfXygSm4jCyNCybVYYK6DwvWqjKee8pbDmJGcLWNDXjh",
+    "ADuUkR4vqLUMWXxW9gh6D6L8pMSawimctcNZ5pGwDcEt",
+    "DttWaMuVvTiduZRnguLF7jNxTgiMBZ1hyAumKUiL2KRL",
+    "3AVi9Tg9Uo68tJfuvoKvqKNWKkC5wPdSSdeBnizKZ6jT",
+];
+
+// Jito bundles hold at most five transactions, landed back to back.
+const MAX_BUNDLE_SIZE: usize = 5;
+
+struct TransactionFees {
+    id: String,
+    cu_price: u64,
+    priority_fee: u64,
+    tip_lamports: u64,
+    writable_accounts: HashSet<String>,
+}
+
+#[substreams::handlers::map]
+fn map_fee_market(block: Block) -> Result<FeeMarkets, substreams::errors::Error> {
+    let block_time = block
+        .block_time
+        .as_ref()
+        .map_or(0, |block_time| block_time.timestamp);
+
+    let decoded_vote_account = bs58::decode(VOTE_ACCOUNT)
+        .into_vec()
+        .expect("Failed to decode vote account");
+
+    let transactions: Vec<TransactionFees> = block
+        .transactions
+        .iter()
+        .filter(|trx| {
+            trx.transaction
+                .as_ref()
+                .and_then(|transaction| transaction.message.as_ref())
+                .map_or(false, |message| {
+                    !message.account_keys.contains(&decoded_vote_account)
+                })
+        })
+        .filter_map(get_transaction_fees)
+        .collect();
+
+    let mut cu_prices: Vec<u64> = transactions.iter().map(|trx| trx.cu_price).collect();
+    cu_prices.sort_unstable();
+
+    let total_priority_fee: u64 = transactions.iter().map(|trx| trx.priority_fee).sum();
+    let total_jito_tips: u64 = transactions.iter().map(|trx| trx.tip_lamports).sum();
+    let clusters = get_bundle_clusters(&transactions);
+
+    let fee_market = FeeMarket {
+        block_date: convert_to_date(block_time).unwrap_or_default(),
+        block_time: block_time as u64,
+        block_slot: block.slot,
+        non_vote_transactions: transactions.len() as u64,
+        priced_transactions: cu_prices.iter().filter(|price| **price > 0).count() as u64,
+        cu_price_p25: get_percentile(&cu_prices, 25),
+        cu_price_p50: get_percentile(&cu_prices, 50),
+        cu_price_p75: get_percentile(&cu_prices, 75),
+        cu_price_p95: get_percentile(&cu_prices, 95),
+        total_priority_fee,
+        total_jito_tips,
+        tip_transactions: transactions
+            .iter()
+            .filter(|trx| trx.tip_lamports > 0)
+            .count() as u64,
+        bundle_clusters: clusters.len() as u64,
+        bundled_transactions: clusters
+            .iter()
+            .map(|cluster| cluster.tx_ids.len() as u64)
+            .sum(),
+        tip_to_priority_fee_ratio: if total_priority_fee > 0 {
+            total_jito_tips as f64 / total_priority_fee as f64
+        } else {
+            0.0
+        },
+        clusters,
+    };
+
+    Ok(FeeMarkets {
+        data: vec![fee_market],
+    })
+}
+
+fn get_transaction_fees(trx: &ConfirmedTransaction) -> Option<TransactionFees> {
+    let meta = trx.meta.as_ref()?;
+    let transaction = trx.transaction.as_ref()?;
+    let message = transaction.message.as_ref()?;
+    let accounts = trx.resolved_accounts_as_strings();
+
+    let mut cu_price = 0;
+    for inst in message.instructions.iter() {
+        if accounts[inst.program_id_index as usize] == COMPUTE_BUDGET_PROGRAM
+            && inst.data.len() >= 9
+            && inst.data[0] == SET_COMPUTE_UNIT_PRICE
+        {
+            cu_price = u64::from_le_bytes(inst.data[1..9].try_into().unwrap());
+        }
+    }
+
+    // Tips are paid with plain system transfers, either directly or through a CPI.
+    let instructions = message
+        .instructions
+        .iter()
+        .map(|inst| (inst.program_id_index, &inst.data, &inst.accounts))
+        .chain(
+            meta.inner_instructions
+                .iter()
+                .flat_map(|inner_instruction| inner_instruction.instructions.iter())
+                .map(|inst| (inst.program_id_index, &inst.data, &inst.accounts)),
+        );
+    let mut tip_lamports = 0;
+    if meta.err.is_none() {
+        for (program_id_index, data, account_indices) in instructions {
+            if accounts[program_id_index as usize] != SYSTEM_PROGRAM
+                || data.len() < 12
+                || account_indices.len() < 2
+                || u32::from_le_bytes(data[0..4].try_into().unwrap()) != SYSTEM_TRANSFER
+            {
+                continue;
+            }
+            if JITO_TIP_ACCOUNTS.contains(&accounts[account_indices[1] as usize].as_str()) {
+                tip_lamports += u64::from_le_bytes(data[4..12].try_into().unwrap());
+            }
+        }
+    }
+
+    Some(TransactionFees {
+        id: bs58::encode(&transaction.signatures[0]).into_string(),
+        cu_price,
+        priority_fee: get_priority_fee(meta.fee, transaction).unwrap_or(0),
+        tip_lamports,
+        writable_accounts: get_writable_accounts(trx).into_iter().collect(),
+    })
+}
+
+// A tipping transaction pulls in the transactions right before it as long as each one
+// writes an account that a later member of the cluster also writes.
+fn get_bundle_clusters(transactions: &Vec<TransactionFees>) -> Vec<BundleCluster> {
+    let mut clusters = vec![];
+    let mut clustered: HashSet<usize> = HashSet::new();
+
+    for (tip_index, tip_trx) in transactions.iter().enumerate() {
+        if tip_trx.tip_lamports == 0 || clustered.contains(&tip_index) {
+            continue;
+        }
+
+        let mut members = vec![tip_index];
+        let mut touched = tip_trx.writable_accounts.clone();
+        let mut index = tip_index;
+        while index > 0 && members.len() < MAX_BUNDLE_SIZE {
+            index -= 1;
+            let trx = &transactions[index];
+            if clustered.contains(&index) || trx.writable_accounts.is_disjoint(&touched) {
+                break;
+            }
+            touched.extend(trx.writable_accounts.iter().cloned());
+            members.push(index);
+        }
+
+        members.reverse();
+        clustered.extend(members.iter());
+        clusters.push(BundleCluster {
+            tip_tx_id: tip_trx.id.to_string(),
+            tip_lamports: tip_trx.tip_lamports,
+            tx_ids: members
+                .iter()
+                .map(|member| transactions[*member].id.to_string())
+                .collect(),
+        });
+    }
+
+    clusters
+}
+
+// Nearest-rank percentile over sorted values.
+fn get_percentile(sorted_values: &Vec<u64>, percentile: usize) -> u64 {
+    if sorted_values.is_empty() {
+        return 0;
+    }
+    let rank = (percentile * sorted_values.len() + 99) / 100;
+    sorted_values[rank.max(1) - 1]
+}
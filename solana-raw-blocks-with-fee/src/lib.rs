@@ -1,3 +1,4 @@
+mod fee_market;
 mod pb;
 mod utils;
 
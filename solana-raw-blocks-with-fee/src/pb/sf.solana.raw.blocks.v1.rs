@@ -55,4 +55,56 @@ pub struct BlockStat {
     #[prost(uint64, required, tag="24")]
     pub failed_non_vote_transactions_priority_fee: u64,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeeMarkets {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<FeeMarket>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FeeMarket {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="2")]
+    pub block_time: u64,
+    #[prost(uint64, required, tag="3")]
+    pub block_slot: u64,
+    #[prost(uint64, required, tag="4")]
+    pub non_vote_transactions: u64,
+    #[prost(uint64, required, tag="5")]
+    pub priced_transactions: u64,
+    #[prost(uint64, required, tag="6")]
+    pub cu_price_p25: u64,
+    #[prost(uint64, required, tag="7")]
+    pub cu_price_p50: u64,
+    #[prost(uint64, required, tag="8")]
+    pub cu_price_p75: u64,
+    #[prost(uint64, required, tag="9")]
+    pub cu_price_p95: u64,
+    #[prost(uint64, required, tag="10")]
+    pub total_priority_fee: u64,
+    #[prost(uint64, required, tag="11")]
+    pub total_jito_tips: u64,
+    #[prost(uint64, required, tag="12")]
+    pub tip_transactions: u64,
+    #[prost(uint64, required, tag="13")]
+    pub bundle_clusters: u64,
+    #[prost(uint64, required, tag="14")]
+    pub bundled_transactions: u64,
+    #[prost(double, required, tag="15")]
+    pub tip_to_priority_fee_ratio: f64,
+    #[prost(message, repeated, tag="16")]
+    pub clusters: ::prost::alloc::vec::Vec<BundleCluster>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BundleCluster {
+    #[prost(string, required, tag="1")]
+    pub tip_tx_id: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="2")]
+    pub tip_lamports: u64,
+    #[prost(string, repeated, tag="3")]
+    pub tx_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
 // @@protoc_insertion_point(module)
@@ -1,4 +1,5 @@
 mod pb;
+mod slot_checks;
 mod utils;
 
 use pb::sf::solana::raw::blocks::v1::{BlockStat, Output};
@@ -41,4 +41,46 @@ pub struct BlockStat {
     #[prost(uint64, required, tag="17")]
     pub failed_non_vote_transactions: u64,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SlotChecks {
+    #[prost(message, repeated, tag="1")]
+    pub skipped_slots: ::prost::alloc::vec::Vec<SkippedSlots>,
+    #[prost(message, repeated, tag="2")]
+    pub inconsistencies: ::prost::alloc::vec::Vec<ChainInconsistency>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SkippedSlots {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="2")]
+    pub block_slot: u64,
+    #[prost(uint64, required, tag="3")]
+    pub from: u64,
+    #[prost(uint64, required, tag="4")]
+    pub to: u64,
+    #[prost(uint64, required, tag="5")]
+    pub count: u64,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChainInconsistency {
+    #[prost(string, required, tag="1")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="2")]
+    pub block_slot: u64,
+    #[prost(string, required, tag="3")]
+    pub block_hash: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="4")]
+    pub parent_slot: u64,
+    #[prost(string, required, tag="5")]
+    pub previous_block_hash: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="6")]
+    pub last_slot: u64,
+    #[prost(string, required, tag="7")]
+    pub last_block_hash: ::prost::alloc::string::String,
+    #[prost(string, required, tag="8")]
+    pub kind: ::prost::alloc::string::String,
+}
 // @@protoc_insertion_point(module)
@@ -0,0 +1,79 @@
+use substreams::store::{DeltaString, Deltas, StoreNew, StoreSet, StoreSetString};
+
+use crate::pb::sf::solana::raw::blocks::v1::{
+    ChainInconsistency, Output, SkippedSlots, SlotChecks,
+};
+
+const LAST_BLOCK_KEY: &str = "last_block";
+
+#[substreams::handlers::store]
+fn store_last_block(output: Output, store: StoreSetString) {
+    for block_stat in output.data.iter() {
+        store.set(
+            0,
+            LAST_BLOCK_KEY,
+            &format!("{}:{}", block_stat.block_slot, block_stat.block_hash),
+        );
+    }
+}
+
+// The delta's old value is the block processed before this one. A gap between parent
+// and slot is a leader skip, while a parent other than the last processed block means
+// blocks were missed on our side or a fork was switched.
+#[substreams::handlers::map]
+fn map_slot_checks(
+    output: Output,
+    last_block_deltas: Deltas<DeltaString>,
+) -> Result<SlotChecks, substreams::errors::Error> {
+    let mut skipped_slots: Vec<SkippedSlots> = vec![];
+    let mut inconsistencies: Vec<ChainInconsistency> = vec![];
+
+    let last_block = last_block_deltas
+        .deltas
+        .iter()
+        .find(|delta| delta.key == LAST_BLOCK_KEY)
+        .and_then(|delta| {
+            let (slot, hash) = delta.old_value.split_once(':')?;
+            Some((slot.parse::<u64>().ok()?, hash.to_string()))
+        });
+
+    for block_stat in output.data.iter() {
+        if block_stat.parent_slot + 1 < block_stat.block_slot {
+            skipped_slots.push(SkippedSlots {
+                block_date: block_stat.block_date.to_string(),
+                block_slot: block_stat.block_slot,
+                from: block_stat.parent_slot + 1,
+                to: block_stat.block_slot - 1,
+                count: block_stat.block_slot - block_stat.parent_slot - 1,
+            });
+        }
+
+        let (last_slot, last_block_hash) = match last_block.as_ref() {
+            Some(last_block) => last_block,
+            None => continue,
+        };
+        let kind = if *last_slot != block_stat.parent_slot {
+            "ingestion_gap"
+        } else if *last_block_hash != block_stat.previous_block_hash {
+            "hash_mismatch"
+        } else {
+            continue;
+        };
+
+        inconsistencies.push(ChainInconsistency {
+            block_date: block_stat.block_date.to_string(),
+            block_slot: block_stat.block_slot,
+            block_hash: block_stat.block_hash.to_string(),
+            parent_slot: block_stat.parent_slot,
+            previous_block_hash: block_stat.previous_block_hash.to_string(),
+            last_slot: *last_slot,
+            last_block_hash: last_block_hash.to_string(),
+            kind: kind.to_string(),
+        });
+    }
+
+    Ok(SlotChecks {
+        skipped_slots,
+        inconsistencies,
+    })
+}
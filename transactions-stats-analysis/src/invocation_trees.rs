@@ -0,0 +1,149 @@
+use log_tree_parser::{parse_logs, LogContext};
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+
+use crate::pb::sf::solana::transaction_stats::v1::{
+    Invocation, InvocationTrees, TransactionInvocations,
+};
+use crate::utils::convert_to_date;
+
+// Unlike map_block, failed transactions are kept, they are the ones worth debugging.
+#[substreams::handlers::map]
+fn map_invocation_trees(block: Block) -> Result<InvocationTrees, substreams::errors::Error> {
+    let block_time = block
+        .block_time
+        .as_ref()
+        .map_or(0, |block_time| block_time.timestamp);
+    let block_date = convert_to_date(block_time).unwrap_or_default();
+    let mut data = Vec::new();
+
+    for trx in block.transactions.iter() {
+        let (meta, transaction) = match (trx.meta.as_ref(), trx.transaction.as_ref()) {
+            (Some(meta), Some(transaction)) => (meta, transaction),
+            _ => continue,
+        };
+
+        let logs_truncated = meta
+            .log_messages
+            .iter()
+            .any(|message| message.to_lowercase().contains("log truncated"));
+
+        let mut invocations = Vec::new();
+        for (instruction_index, root) in parse_logs(&meta.log_messages).iter().enumerate() {
+            let mut inner_instruction_index = 0;
+            add_invocation(
+                root,
+                None,
+                // Truncated logs lose invocations, the tree can't be matched to the
+                // instructions anymore.
+                (!logs_truncated).then_some(instruction_index as u32),
+                &mut inner_instruction_index,
+                &mut invocations,
+            );
+        }
+
+        data.push(TransactionInvocations {
+            block_slot: block.slot as u32,
+            block_time: block_time as u64,
+            block_date: block_date.to_string(),
+            id: bs58::encode(&transaction.signatures[0]).into_string(),
+            success: meta.err.is_none(),
+            logs_truncated,
+            invocations,
+        });
+    }
+
+    Ok(InvocationTrees { data })
+}
+
+// Nodes are added in invocation order, so the n-th descendant of an outer instruction
+// is the n-th entry of its inner instructions in the transaction meta.
+fn add_invocation(
+    node: &LogContext,
+    parent_index: Option<u32>,
+    instruction_index: Option<u32>,
+    inner_instruction_index: &mut u32,
+    invocations: &mut Vec<Invocation>,
+) {
+    let index = invocations.len() as u32;
+    invocations.push(Invocation {
+        index,
+        parent_index,
+        instruction_index,
+        inner_instruction_index: instruction_index.and(parent_index).map(|_| {
+            *inner_instruction_index += 1;
+            *inner_instruction_index - 1
+        }),
+        program_id: node.program_id.to_string(),
+        depth: node.depth as u32,
+        compute_units_consumed: node.consumed_units as u64,
+        compute_units_allocated: node.compute_units as u64,
+        success: node.success,
+        failure_message: node.failure_message.clone(),
+        program_logs: node.program_logs.clone(),
+        program_data: node.program_data.clone(),
+    });
+
+    for child in &node.children_nodes {
+        add_invocation(
+            child,
+            Some(index),
+            instruction_index,
+            inner_instruction_index,
+            invocations,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_inner_instructions_in_invocation_order() {
+        let log_messages: Vec<String> = [
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 invoke [1]",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc invoke [2]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA invoke [3]",
+            "Program TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA success",
+            "Program whirLbMiicVdio4qvUfM5KAg6Ct8VwpYzGff3uctyCc success",
+            "Program 11111111111111111111111111111111 invoke [2]",
+            "Program 11111111111111111111111111111111 success",
+            "Program JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4 success",
+        ]
+        .iter()
+        .map(|message| message.to_string())
+        .collect();
+
+        let mut invocations = Vec::new();
+        for root in parse_logs(&log_messages).iter() {
+            let mut inner_instruction_index = 0;
+            add_invocation(
+                root,
+                None,
+                Some(3),
+                &mut inner_instruction_index,
+                &mut invocations,
+            );
+        }
+
+        assert_eq!(
+            invocations
+                .iter()
+                .map(|invocation| (
+                    invocation.parent_index,
+                    invocation.inner_instruction_index,
+                    invocation.depth
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (None, None, 1),
+                (Some(0), Some(0), 2),
+                (Some(1), Some(1), 3),
+                (Some(0), Some(2), 2),
+            ]
+        );
+        assert!(invocations
+            .iter()
+            .all(|invocation| invocation.instruction_index == Some(3)));
+    }
+}
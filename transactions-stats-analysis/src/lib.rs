@@ -1,5 +1,7 @@
+mod invocation_trees;
 mod pb;
 mod utils;
+mod write_locks;
 use std::cmp;
 
 use std::{collections::HashSet, io::Read};
@@ -7,17 +9,22 @@ use std::{collections::HashSet, io::Read};
 use bytes::Buf;
 use pb::sf::solana::transaction_stats::v1::{Instruction, Output, TransactionStats};
 
+use log_tree_parser::{parse_logs, LogContext};
 use substreams_solana::pb::sf::solana::r#type::v1::{
     Block, CompiledInstruction, InnerInstructions, Message, MessageHeader, Transaction,
     TransactionStatusMeta,
 };
 use substreams_solana_core::base58;
-use utils::{
-    calculate_byte_size, calculate_instruction_size, compact_array_size, convert_to_date,
-    parse_logs, LogContext,
-};
+use utils::{calculate_byte_size, calculate_instruction_size, compact_array_size, convert_to_date};
 
 const VOTE_ACCOUNT: &str = "Vote111111111111111111111111111111111111111";
+const COMPUTE_BUDGET_PROGRAM: &str = "ComputeBudget111111111111111111111111111111";
+
+// ComputeBudget instruction discriminators, 0 is the deprecated RequestUnits.
+const REQUEST_HEAP_FRAME: u8 = 1;
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+const SET_LOADED_ACCOUNTS_DATA_SIZE_LIMIT: u8 = 4;
 
 #[substreams::handlers::map]
 fn map_block(block: Block) -> Result<Output, substreams::errors::Error> {
@@ -180,11 +187,24 @@ fn update_transaction_stats_compute_units(
     for (idx, inst) in msg.instructions.into_iter().enumerate() {
         let program = &accounts[inst.program_id_index as usize];
 
-        if program.eq("ComputeBudget111111111111111111111111111111") {
+        if program.eq(COMPUTE_BUDGET_PROGRAM) && !inst.data.is_empty() {
             let (disc_bytes, mut rest_bytes) = inst.data.split_at(1);
             let discriminator: u8 = u8::from(disc_bytes[0]);
-            if discriminator == 2 {
-                compute_units_allocated = rest_bytes.get_u32_le() as u64;
+            match discriminator {
+                REQUEST_HEAP_FRAME if rest_bytes.len() >= 4 => {
+                    transaction_stats.heap_frame_bytes = rest_bytes.get_u32_le();
+                }
+                SET_COMPUTE_UNIT_LIMIT if rest_bytes.len() >= 4 => {
+                    compute_units_allocated = rest_bytes.get_u32_le() as u64;
+                    transaction_stats.compute_unit_limit = compute_units_allocated;
+                }
+                SET_COMPUTE_UNIT_PRICE if rest_bytes.len() >= 8 => {
+                    transaction_stats.compute_unit_price = rest_bytes.get_u64_le();
+                }
+                SET_LOADED_ACCOUNTS_DATA_SIZE_LIMIT if rest_bytes.len() >= 4 => {
+                    transaction_stats.loaded_accounts_data_size_limit = rest_bytes.get_u32_le();
+                }
+                _ => {}
             }
         }
     }
@@ -193,6 +213,10 @@ fn update_transaction_stats_compute_units(
 
     if let Some(compute_units) = meta.compute_units_consumed {
         transaction_stats.compute_units_consumed = compute_units;
+        if compute_units > 0 {
+            transaction_stats.cu_over_request_ratio =
+                compute_units_allocated as f64 / compute_units as f64;
+        }
     }
 }
 
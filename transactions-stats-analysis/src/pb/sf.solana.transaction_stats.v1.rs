@@ -46,6 +46,16 @@ pub struct TransactionStats {
     pub signatures_size: u32,
     #[prost(string, required, tag="20")]
     pub signer: ::prost::alloc::string::String,
+    #[prost(uint64, required, tag="21")]
+    pub compute_unit_limit: u64,
+    #[prost(uint64, required, tag="22")]
+    pub compute_unit_price: u64,
+    #[prost(uint32, required, tag="23")]
+    pub heap_frame_bytes: u32,
+    #[prost(uint32, required, tag="24")]
+    pub loaded_accounts_data_size_limit: u32,
+    #[prost(double, required, tag="25")]
+    pub cu_over_request_ratio: f64,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -59,4 +69,92 @@ pub struct Instruction {
     #[prost(uint32, required, tag="4")]
     pub data_bytes: u32,
 }
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InvocationTrees {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<TransactionInvocations>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionInvocations {
+    #[prost(uint32, required, tag="1")]
+    pub block_slot: u32,
+    #[prost(uint64, required, tag="2")]
+    pub block_time: u64,
+    #[prost(string, required, tag="3")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(string, required, tag="4")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(bool, required, tag="5")]
+    pub success: bool,
+    #[prost(bool, required, tag="6")]
+    pub logs_truncated: bool,
+    #[prost(message, repeated, tag="7")]
+    pub invocations: ::prost::alloc::vec::Vec<Invocation>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Invocation {
+    #[prost(uint32, required, tag="1")]
+    pub index: u32,
+    #[prost(uint32, optional, tag="2")]
+    pub parent_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="3")]
+    pub instruction_index: ::core::option::Option<u32>,
+    #[prost(uint32, optional, tag="4")]
+    pub inner_instruction_index: ::core::option::Option<u32>,
+    #[prost(string, required, tag="5")]
+    pub program_id: ::prost::alloc::string::String,
+    #[prost(uint32, required, tag="6")]
+    pub depth: u32,
+    #[prost(uint64, required, tag="7")]
+    pub compute_units_consumed: u64,
+    #[prost(uint64, required, tag="8")]
+    pub compute_units_allocated: u64,
+    #[prost(bool, required, tag="9")]
+    pub success: bool,
+    #[prost(string, optional, tag="10")]
+    pub failure_message: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag="11")]
+    pub program_logs: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(string, repeated, tag="12")]
+    pub program_data: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteLocks {
+    #[prost(message, repeated, tag="1")]
+    pub data: ::prost::alloc::vec::Vec<BlockWriteLocks>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlockWriteLocks {
+    #[prost(uint32, required, tag="1")]
+    pub block_slot: u32,
+    #[prost(uint64, required, tag="2")]
+    pub block_time: u64,
+    #[prost(string, required, tag="3")]
+    pub block_date: ::prost::alloc::string::String,
+    #[prost(uint32, required, tag="4")]
+    pub transactions_count: u32,
+    #[prost(uint32, required, tag="5")]
+    pub write_locked_accounts_count: u32,
+    #[prost(message, repeated, tag="6")]
+    pub accounts: ::prost::alloc::vec::Vec<WriteLockedAccount>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteLockedAccount {
+    #[prost(string, required, tag="1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(uint32, required, tag="2")]
+    pub transactions_count: u32,
+    #[prost(uint32, required, tag="3")]
+    pub failed_transactions_count: u32,
+    #[prost(uint64, required, tag="4")]
+    pub compute_units_consumed: u64,
+    #[prost(double, required, tag="5")]
+    pub failure_rate: f64,
+}
 // @@protoc_insertion_point(module)
@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use substreams_solana::pb::sf::solana::r#type::v1::Block;
+use writable_accounts::get_writable_accounts;
+
+use crate::pb::sf::solana::transaction_stats::v1::{
+    BlockWriteLocks, WriteLockedAccount, WriteLocks,
+};
+use crate::utils::convert_to_date;
+
+const TOP_ACCOUNTS: usize = 20;
+
+#[derive(Default)]
+struct AccountLocks {
+    transactions_count: u32,
+    failed_transactions_count: u32,
+    compute_units_consumed: u64,
+}
+
+// Transactions writing the same account are serialized by the scheduler, so the accounts
+// write-locked by the most transactions of a block are its throughput bottlenecks. Only
+// accounts locked by at least two transactions are contended.
+#[substreams::handlers::map]
+fn map_write_locks(block: Block) -> Result<WriteLocks, substreams::errors::Error> {
+    let block_time = block
+        .block_time
+        .as_ref()
+        .map_or(0, |block_time| block_time.timestamp);
+
+    let mut account_locks: HashMap<String, AccountLocks> = HashMap::new();
+    let mut transactions_count = 0;
+    for trx in block.transactions.iter() {
+        let meta = match trx.meta.as_ref() {
+            Some(meta) => meta,
+            None => continue,
+        };
+        transactions_count += 1;
+
+        for account in get_writable_accounts(trx) {
+            let locks = account_locks.entry(account).or_default();
+            locks.transactions_count += 1;
+            locks.compute_units_consumed += meta.compute_units_consumed.unwrap_or(0);
+            if meta.err.is_some() {
+                locks.failed_transactions_count += 1;
+            }
+        }
+    }
+
+    let write_locked_accounts_count = account_locks.len() as u32;
+    let mut accounts: Vec<WriteLockedAccount> = account_locks
+        .into_iter()
+        .filter(|(_, locks)| locks.transactions_count > 1)
+        .map(|(account, locks)| WriteLockedAccount {
+            account,
+            transactions_count: locks.transactions_count,
+            failed_transactions_count: locks.failed_transactions_count,
+            compute_units_consumed: locks.compute_units_consumed,
+            failure_rate: locks.failed_transactions_count as f64 / locks.transactions_count as f64,
+        })
+        .collect();
+    accounts.sort_by(|a, b| {
+        b.transactions_count
+            .cmp(&a.transactions_count)
+            .then(b.compute_units_consumed.cmp(&a.compute_units_consumed))
+            .then(a.account.cmp(&b.account))
+    });
+    accounts.truncate(TOP_ACCOUNTS);
+
+    Ok(WriteLocks {
+        data: vec![BlockWriteLocks {
+            block_slot: block.slot as u32,
+            block_time: block_time as u64,
+            block_date: convert_to_date(block_time).unwrap_or_default(),
+            transactions_count,
+            write_locked_accounts_count,
+            accounts,
+        }],
+    })
+}
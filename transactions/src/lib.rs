@@ -6,6 +6,8 @@ use pb::sf::solana::transactions::v1::{Error, Output, TransactionStats};
 
 use std::collections::HashSet;
 
+use program_errors::{decode_error, parse_error_code};
+
 use substreams_solana::pb::sf::solana::r#type::v1::{
     Block, CompiledInstruction, InnerInstruction, Message, MessageHeader, TokenBalance,
     Transaction, TransactionStatusMeta,
@@ -159,6 +161,21 @@ fn find_error(log_contexts: &[LogContext], meta: &TransactionStatusMeta) -> Opti
         if let Some(ref message) = log_context.failure_message {
             error.program = log_context.program_id.clone();
             error.message_decoded = message.clone();
+            error.code = parse_error_code(message);
+
+            // Callers log the failure again, the code belongs to the innermost failing program.
+            let mut origin = log_context;
+            while let Some(child) = origin
+                .children_nodes
+                .iter()
+                .find(|child| child.failure_message.is_some())
+            {
+                origin = child;
+            }
+            if let Some(program_error) = decode_error(&origin.program_id, message) {
+                error.name = Some(program_error.name.clone());
+                error.description = Some(program_error.message.clone());
+            }
             return Some(error);
         }
 
@@ -84,6 +84,12 @@ pub struct Error {
     pub message: ::prost::alloc::string::String,
     #[prost(string, required, tag="3")]
     pub message_decoded: ::prost::alloc::string::String,
+    #[prost(uint32, optional, tag="4")]
+    pub code: ::core::option::Option<u32>,
+    #[prost(string, optional, tag="5")]
+    pub name: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag="6")]
+    pub description: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -0,0 +1,91 @@
+// Resolves the accounts a transaction actually write-locks. The message header only says
+// which keys were requested as writable, the runtime then demotes some of them to
+// read-only before scheduling.
+use substreams_solana::pb::sf::solana::r#type::v1::ConfirmedTransaction;
+
+const BPF_LOADER_UPGRADEABLE: &str = "BPFLoaderUpgradeab1e11111111111111111111111";
+
+// Builtin programs and sysvars can never be write-locked by a transaction.
+const RESERVED_ACCOUNTS: [&str; 29] = [
+    "11111111111111111111111111111111",
+    "AddressLookupTab1e1111111111111111111111111",
+    "BPFLoader1111111111111111111111111111111111",
+    "BPFLoader2111111111111111111111111111111111",
+    BPF_LOADER_UPGRADEABLE,
+    "ComputeBudget111111111111111111111111111111",
+    "Config1111111111111111111111111111111111111",
+    "Ed25519SigVerify111111111111111111111111111",
+    "Feature111111111111111111111111111111111111",
+    "KeccakSecp256k11111111111111111111111111111",
+    "LoaderV411111111111111111111111111111111111",
+    "NativeLoader1111111111111111111111111111111",
+    "Secp256r1SigVerify1111111111111111111111111",
+    "Stake11111111111111111111111111111111111111",
+    "Vote111111111111111111111111111111111111111",
+    "ZkE1Gama1Proof11111111111111111111111111111",
+    "ZkTokenProof1111111111111111111111111111111",
+    "Sysvar1nstructions1111111111111111111111111",
+    "SysvarC1ock11111111111111111111111111111111",
+    "SysvarEpochRewards1111111111111111111111111",
+    "SysvarEpochSchedu1e111111111111111111111111",
+    "SysvarFees111111111111111111111111111111111",
+    "SysvarLastRestartS1ot1111111111111111111111",
+    "SysvarRecentB1ockHashes11111111111111111111",
+    "SysvarRent111111111111111111111111111111111",
+    "SysvarRewards111111111111111111111111111111",
+    "SysvarS1otHashes111111111111111111111111111",
+    "SysvarS1otHistory11111111111111111111111111",
+    "SysvarStakeHistory1111111111111111111111111",
+];
+
+// Signed writable keys come first, then unsigned writable keys, then loaded writable
+// addresses right after the static keys. Reserved accounts are demoted, and so are the
+// programs invoked by the outer instructions unless the upgradeable loader is loaded,
+// since upgrading a program has to write its program account.
+pub fn get_writable_accounts(trx: &ConfirmedTransaction) -> Vec<String> {
+    let (meta, message) = match (
+        trx.meta.as_ref(),
+        trx.transaction
+            .as_ref()
+            .and_then(|transaction| transaction.message.as_ref()),
+    ) {
+        (Some(meta), Some(message)) => (meta, message),
+        _ => return vec![],
+    };
+    let header = match message.header.as_ref() {
+        Some(header) => header,
+        None => return vec![],
+    };
+
+    let accounts: Vec<String> = trx
+        .resolved_accounts()
+        .iter()
+        .map(|account| bs58::encode(account).into_string())
+        .collect();
+    let demote_program_ids = !accounts
+        .iter()
+        .any(|account| account == BPF_LOADER_UPGRADEABLE);
+
+    let num_signed = header.num_required_signatures as usize;
+    let num_static = message.account_keys.len();
+    accounts
+        .into_iter()
+        .enumerate()
+        .filter(|(index, account)| {
+            let requested = if *index < num_signed {
+                *index < num_signed.saturating_sub(header.num_readonly_signed_accounts as usize)
+            } else if *index < num_static {
+                *index < num_static.saturating_sub(header.num_readonly_unsigned_accounts as usize)
+            } else {
+                *index < num_static + meta.loaded_writable_addresses.len()
+            };
+            let is_program_id = demote_program_ids
+                && message
+                    .instructions
+                    .iter()
+                    .any(|inst| inst.program_id_index as usize == *index);
+            requested && !is_program_id && !RESERVED_ACCOUNTS.contains(&account.as_str())
+        })
+        .map(|(_, account)| account)
+        .collect()
+}